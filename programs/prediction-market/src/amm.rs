@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::lmsr::to_amount;
+
+// Constant-product pool over outcome shares. One token always mints one share
+// of every outcome, so `Market::pools` holds the same total for each outcome
+// and the pool's `reserves` are a slice of it. A swap keeps the product of
+// the reserves constant, the LP fee stays in the pool.
+
+/// Swap fee of a constant-product market in basis points
+pub fn lp_fee_of(market: &Market) -> Result<u16> {
+    match market.pricing {
+        PricingMode::ConstantProduct { fee_bps } => Ok(fee_bps),
+        _ => err!(PredictionMarketError::UnsupportedPricingMode),
+    }
+}
+
+/// Shares of `to` paid out for `amount_in` shares of `from`, and the part of
+/// `amount_in` kept as the LP fee
+pub fn swap_output(market: &Market, from: usize, to: usize, amount_in: u64) -> Result<(u64, u64)> {
+    let fee_bps = lp_fee_of(market)?;
+    let reserve_in = market.reserves[from] as u128;
+    let reserve_out = market.reserves[to] as u128;
+    require!(
+        reserve_in > 0 && reserve_out > 0,
+        PredictionMarketError::InsufficientLiquidity
+    );
+
+    let fee = (amount_in as u128 * fee_bps as u128 / BASIS_POINT_DIVIDER as u128) as u64;
+    let amount_in_after_fee = (amount_in - fee) as u128;
+
+    // Round the remaining reserve up so the product never shrinks
+    let product = reserve_in
+        .checked_mul(reserve_out)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let new_reserve_in = reserve_in + amount_in_after_fee;
+    let new_reserve_out = product.div_ceil(new_reserve_in);
+
+    Ok(((reserve_out - new_reserve_out) as u64, fee))
+}
+
+fn reserve_prices(market: &Market) -> Vec<f64> {
+    let outcome_count = market.reserves.len();
+    if market.lp_supply == 0 || market.reserves.contains(&0) {
+        return vec![1.0 / outcome_count as f64; outcome_count];
+    }
+
+    // An outcome gets cheaper the more of it the pool holds
+    let weights = market.reserves
+        .iter()
+        .map(|reserve| 1.0 / *reserve as f64)
+        .collect::<Vec<f64>>();
+    let sum: f64 = weights.iter().sum();
+
+    weights.iter().map(|weight| weight / sum).collect()
+}
+
+/// Current price of every outcome scaled by PRICE_SCALE
+pub fn scaled_reserve_prices(market: &Market) -> Result<Vec<u64>> {
+    reserve_prices(market)
+        .iter()
+        .map(|price| to_amount((price * PRICE_SCALE as f64).round()))
+        .collect()
+}
+
+/// Settle a voided constant-product market at the pool's last prices. The
+/// prices sum to one, so the payouts never exceed the collateral.
+pub fn settle_at_reserve_prices(market: &mut Market) -> Result<()> {
+    let payouts = reserve_prices(market)
+        .iter()
+        .zip(market.pools.iter())
+        .map(|(price, shares)| to_amount((price * *shares as f64).floor()))
+        .collect::<Result<Vec<u64>>>()?;
+
+    market.payouts = payouts;
+    market.refund_mode = true;
+    Ok(())
+}
@@ -32,4 +32,178 @@ pub enum PredictionMarketError {
     InvalidOracleValue,
     #[msg("Invalid amount")]
     InvalidAmount,
+    #[msg("Market has been voided")]
+    MarketVoided,
+    #[msg("Market has not been voided")]
+    MarketNotVoided,
+    #[msg("Unauthorized to cancel this market")]
+    UnauthorizedCanceller,
+    #[msg("No refund to claim")]
+    NoRefundToClaim,
+    #[msg("Nobody bet on the winning outcome")]
+    EmptyWinningPool,
+    #[msg("Invalid outcome for this market")]
+    InvalidOutcome,
+    #[msg("Invalid number of outcomes for this market")]
+    InvalidOutcomeCount,
+    #[msg("Outcome label is too long")]
+    OutcomeLabelTooLong,
+    #[msg("Invalid market type for this instruction")]
+    InvalidMarketType,
+    #[msg("Invalid line for this market type")]
+    InvalidLine,
+    #[msg("Invalid correct score grid size")]
+    InvalidScoreGrid,
+    #[msg("Invalid bounds for a scalar market")]
+    InvalidScalarBounds,
+    #[msg("Invalid legs for a parlay")]
+    InvalidParlayLegs,
+    #[msg("Parlay leg's market has too little staked to price it")]
+    LegNotPriceable,
+    #[msg("Parlay payout exceeds the pool's limit or available liquidity")]
+    InsufficientParlayLiquidity,
+    #[msg("Parlay leg has already been settled")]
+    LegAlreadySettled,
+    #[msg("Market does not match the parlay leg")]
+    LegMarketMismatch,
+    #[msg("Parlay is not open")]
+    ParlayNotOpen,
+    #[msg("Parlay has not been settled")]
+    ParlayNotSettled,
+    #[msg("Unauthorized to manage this parlay pool")]
+    UnauthorizedParlayPool,
+    #[msg("Category is too long")]
+    CategoryTooLong,
+    #[msg("Invalid parent market for this conditional market")]
+    InvalidParentMarket,
+    #[msg("Market is not conditional on a parent market")]
+    NotConditionalMarket,
+    #[msg("Parent market is not settled yet")]
+    ParentNotResolved,
+    #[msg("Parent market did not resolve to the required outcome")]
+    ParentConditionNotMet,
+    #[msg("Parent market resolved to the required outcome")]
+    ParentConditionMet,
+    #[msg("Instruction does not support this market's pricing mode")]
+    UnsupportedPricingMode,
+    #[msg("Invalid liquidity parameter")]
+    InvalidLiquidity,
+    #[msg("Trade price moved past the caller's limit")]
+    SlippageExceeded,
+    #[msg("Not enough shares to sell")]
+    InsufficientShares,
+    #[msg("No liquidity left to withdraw")]
+    NoLiquidityToWithdraw,
+    #[msg("Unauthorized to withdraw this market's liquidity")]
+    UnauthorizedLiquidityWithdrawal,
+    #[msg("Liquidity provider fee is above the maximum")]
+    InvalidLpFee,
+    #[msg("Not enough pool liquidity for this trade")]
+    InsufficientLiquidity,
+    #[msg("Not enough LP shares to remove")]
+    InsufficientLpShares,
+    #[msg("Odds are outside the allowed range")]
+    InvalidOdds,
+    #[msg("Order book has no room for another resting order")]
+    OrderBookFull,
+    #[msg("Order not found")]
+    OrderNotFound,
+    #[msg("Only the owner can cancel an order before the market starts")]
+    UnauthorizedOrderCancel,
+    #[msg("Bet would take the house past its exposure cap on this outcome")]
+    ExposureCapExceeded,
+    #[msg("Not enough house liquidity to cover this bet")]
+    InsufficientHouseLiquidity,
+    #[msg("Position has reached the maximum number of fixed-odds bets")]
+    TooManyBets,
+    #[msg("Unauthorized to manage this market's odds or house liquidity")]
+    UnauthorizedBookmaker,
+    #[msg("Fee is above the protocol maximum")]
+    FeeTooHigh,
+    #[msg("Protocol and referrer shares add up to more than the whole fee")]
+    InvalidFeeSplit,
+    #[msg("Unauthorized to manage the protocol config")]
+    UnauthorizedProtocolAdmin,
+    #[msg("Bettors cannot refer themselves")]
+    SelfReferral,
+    #[msg("Betting has closed ahead of the market start")]
+    BettingClosed,
+    #[msg("Projected payout is below the bettor's limit")]
+    PayoutBelowLimit,
+    #[msg("Betting cutoff must be a non-negative number of seconds")]
+    InvalidBettingCutoff,
+    #[msg("Unauthorized to change this market's betting window")]
+    UnauthorizedBettingWindow,
+    #[msg("Fee curve must span the market fee and stay under the protocol maximum")]
+    InvalidFeeCurve,
+    #[msg("Unauthorized to change this market's fees")]
+    UnauthorizedFeeUpdate,
+    #[msg("Time weighting needs a bonus up to the protocol maximum and a positive ramp")]
+    InvalidTimeWeighting,
+    #[msg("Unauthorized to change how this market shares out its pot")]
+    UnauthorizedTimeWeighting,
+    #[msg("Bets have already been placed on this market")]
+    BetsAlreadyPlaced,
+    #[msg("Oracle accounts do not match the market's oracle source")]
+    InvalidOracleSource,
+    #[msg("Oracle price is not currently trading")]
+    OraclePriceUnavailable,
+    #[msg("Result was not signed by the market's registered oracle signer")]
+    UnregisteredOracleSigner,
+    #[msg("Missing or malformed result attestation")]
+    InvalidAttestation,
+    #[msg("Quorum needs 2 to 5 distinct feeds and a majority threshold")]
+    InvalidOracleQuorum,
+    #[msg("Not enough oracle feeds have reported a result yet")]
+    QuorumNotReached,
+    #[msg("Optimistic resolution needs a bond and a challenge window")]
+    InvalidOptimisticConfig,
+    #[msg("A proposed result is awaiting settlement")]
+    ProposalOpen,
+    #[msg("The challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("The challenge window is still open")]
+    ChallengeWindowOpen,
+    #[msg("Proposers cannot dispute their own result")]
+    SelfDispute,
+    #[msg("Proposal is not in a state that allows this")]
+    InvalidProposalStatus,
+    #[msg("Only the market's arbiter can rule on a dispute")]
+    UnauthorizedArbiter,
+    #[msg("Feed checks need a positive staleness limit")]
+    InvalidFeedChecks,
+    #[msg("Oracle result is older than the market allows")]
+    StaleOracleResult,
+    #[msg("Oracle result was built from too few samples")]
+    InsufficientOracleSamples,
+    #[msg("Oracle samples are spread wider than the market allows")]
+    OracleDeviationTooHigh,
+    #[msg("Only the market authority can resolve it by fallback")]
+    UnauthorizedFallback,
+    #[msg("Grace period must be a non-negative number of seconds")]
+    InvalidGracePeriod,
+    #[msg("The oracle's grace period has not run out yet")]
+    GracePeriodActive,
+    #[msg("No fallback result has been announced")]
+    FallbackNotAnnounced,
+    #[msg("The announced fallback result is still timelocked")]
+    TimelockActive,
+    #[msg("Oracle settings are locked once the market has started or taken stakes")]
+    OracleSettingsLocked,
+    #[msg("Price account is not owned by the price oracle program")]
+    InvalidPriceAccountOwner,
+    #[msg("The bettor's volume account is needed to take back the volume this position counted")]
+    MissingUserVolume,
+    #[msg("Parlay margin is above the maximum")]
+    InvalidParlayMargin,
+    #[msg("Order stake is below the minimum")]
+    OrderTooSmall,
+    #[msg("Owner already has the maximum number of resting orders")]
+    TooManyOrders,
+    #[msg("Unmatched stake would rest against a crossing order, pass every crossing maker's position")]
+    OrderWouldCross,
+    #[msg("Program data account does not belong to this program")]
+    InvalidProgramData,
+    #[msg("Outstanding shares of this outcome would exceed the market's cap")]
+    ShareCapExceeded,
 }
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct MarketRefundSettled {
+    pub market: Pubkey,
+    pub result: i64,
+    pub total_pool: u64,
+}
+
+#[event]
+pub struct OracleDisagreement {
+    pub market: Pubkey,
+    pub threshold: u8,
+    /// Result read from each quorum feed, indexed like the market's `quorum_feeds`
+    pub reports: Vec<Option<i64>>,
+}
+
+#[event]
+pub struct ResultProposed {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub result: i64,
+    pub bond: u64,
+    pub challenge_ends_at: i64,
+}
+
+#[event]
+pub struct ProposalDisputed {
+    pub market: Pubkey,
+    pub disputer: Pubkey,
+    pub result: i64,
+}
+
+#[event]
+pub struct ProposalSettled {
+    pub market: Pubkey,
+    /// Result the market settled on, the proposed one unless a dispute overturned it
+    pub result: i64,
+    /// Whoever took the bonds
+    pub winner: Pubkey,
+    pub bonds: u64,
+}
+
+#[event]
+pub struct FallbackResolutionAnnounced {
+    pub market: Pubkey,
+    pub result: i64,
+    /// Earliest the market can be settled on `result`
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct ConditionalMarketVoided {
+    pub market: Pubkey,
+    pub parent_market: Pubkey,
+}
+
+#[event]
+pub struct SharesTraded {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome: u8,
+    pub shares: u64,
+    /// Tokens paid by the buyer or received by the seller, fees excluded
+    pub amount: u64,
+    pub is_buy: bool,
+    /// Price of every outcome after the trade, scaled by PRICE_SCALE
+    pub prices: Vec<u64>,
+}
+
+#[event]
+pub struct OutcomeSwapped {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub from_outcome: u8,
+    pub to_outcome: u8,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Part of `amount_in` left in the pool for liquidity providers
+    pub lp_fee: u64,
+    /// Price of every outcome after the swap, scaled by PRICE_SCALE
+    pub prices: Vec<u64>,
+}
+
+#[event]
+pub struct OrderMatched {
+    pub market: Pubkey,
+    pub outcome: u8,
+    /// Odds of the resting order the bet was matched at
+    pub odds: u64,
+    pub backer: Pubkey,
+    pub layer: Pubkey,
+    pub back_stake: u64,
+    pub lay_liability: u64,
+}
+
+#[event]
+pub struct FeeCharged {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    /// Amount the fee was charged on
+    pub amount: u64,
+    /// Effective rate after the market's fee curve and volume discounts
+    pub fee_bps: u16,
+    pub fee: u64,
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Transfer, transfer};
+use crate::instructions::place_bet::{PlaceBet, accrue_bet_fee, volume_fee_rate, record_volume};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::payout::*;
+
+pub fn provide_liquidity(ctx: Context<PlaceBet>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(
+        clock.unix_timestamp < market.betting_closes_at(),
+        PredictionMarketError::BettingClosed
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(
+        matches!(market.pricing, PricingMode::ConstantProduct { .. }),
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    // Liquidity mints complete sets, so it pays the same protocol fee on entry
+    let fee_bps = volume_fee_rate(ctx.accounts.market.fee_bps, &ctx.accounts.user_volume);
+    let fee_amount = bet_fee(amount, fee_bps)?;
+    let total = amount
+        .checked_add(fee_amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.market_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    transfer(cpi_ctx, total)?;
+
+    accrue_bet_fee(ctx.accounts, fee_amount)?;
+
+    let market = &mut ctx.accounts.market;
+    let market_key = market.key();
+    let position = &mut ctx.accounts.position;
+    if position.user == Pubkey::default() {
+        position.user = ctx.accounts.user.key();
+        position.market = market_key;
+        position.amounts = vec![0; market.pools.len()];
+        position.fees_paid = 0;
+        position.lp_shares = 0;
+        position.exchange_paid = 0;
+        position.volume = 0;
+        position.bets = vec![];
+        position.shares = vec![];
+        position.bump = ctx.bumps.position;
+    }
+
+    // The deposit mints one share of every outcome per token. Only the part
+    // matching the pool's current proportions goes in, so prices do not
+    // move; the rest goes to the provider as outcome shares.
+    let max_reserve = market.reserves.iter().copied().max().unwrap_or(0);
+    let minted = if market.lp_supply == 0 || max_reserve == 0 {
+        market.reserves.iter_mut().for_each(|reserve| *reserve += amount);
+        amount
+    } else {
+        for (reserve, held) in market.reserves.iter_mut().zip(position.amounts.iter_mut()) {
+            let added = (amount as u128 * *reserve as u128 / max_reserve as u128) as u64;
+            *reserve += added;
+            *held = held
+                .checked_add(amount - added)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+        }
+        (amount as u128 * market.lp_supply as u128 / max_reserve as u128) as u64
+    };
+    require!(minted > 0, PredictionMarketError::InvalidAmount);
+
+    for pool in market.pools.iter_mut() {
+        *pool = pool
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    market.collateral = market.collateral
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.lp_supply = market.lp_supply
+        .checked_add(minted)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    position.lp_shares = position.lp_shares
+        .checked_add(minted)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    position.fees_paid = position.fees_paid
+        .checked_add(fee_amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    record_volume(&mut ctx.accounts.user_volume, position, amount)?;
+
+    msg!(
+        "Liquidity added: {} tokens for {} LP shares | Reserves: {:?} | Fee: {} bps",
+        amount,
+        minted,
+        market.reserves,
+        fee_bps
+    );
+
+    emit!(FeeCharged {
+        market: market_key,
+        user: ctx.accounts.user.key(),
+        amount,
+        fee_bps,
+        fee: fee_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::resolve_market::{check_resolvable, check_result};
+
+pub fn announce_fallback(
+    ctx: Context<AnnounceFallbackResolution>,
+    result: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedFallback
+    );
+    check_resolvable(market, ctx.accounts.parent_market.as_ref(), &clock)?;
+    require!(
+        clock.unix_timestamp >= market.resolution_time.saturating_add(market.fallback_grace_period),
+        PredictionMarketError::GracePeriodActive
+    );
+    // An open proposal has bonds riding on it, it has to settle first
+    require!(!market.proposal_open, PredictionMarketError::ProposalOpen);
+    check_result(market, result)?;
+
+    // Announcing again replaces the result and restarts the timelock
+    let executable_at = clock.unix_timestamp
+        .checked_add(FALLBACK_TIMELOCK)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.fallback = Some(FallbackResolution { result, executable_at });
+
+    msg!("Fallback result {} announced, executable from {}", result, executable_at);
+
+    emit!(FallbackResolutionAnnounced {
+        market: market.key(),
+        result,
+        executable_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AnnounceFallbackResolution<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+
+    /// Parent market, only required for a conditional market
+    pub parent_market: Option<Account<'info, Market>>,
+}
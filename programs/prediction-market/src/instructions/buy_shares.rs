@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Transfer, transfer};
+use crate::instructions::place_bet::{PlaceBet, accrue_bet_fee, volume_fee_rate, record_volume};
+use crate::errors::*;
+use crate::events::*;
+use crate::lmsr::*;
+use crate::payout::*;
+
+pub fn buy_shares(ctx: Context<PlaceBet>, outcome_index: u8, shares: u64, max_cost: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(
+        clock.unix_timestamp < market.betting_closes_at(),
+        PredictionMarketError::BettingClosed
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(shares > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        (outcome_index as usize) < market.pools.len(),
+        PredictionMarketError::InvalidOutcome
+    );
+
+    let index = outcome_index as usize;
+    let cost = buy_cost(market, index, shares)?;
+    require!(cost <= max_cost, PredictionMarketError::SlippageExceeded);
+
+    let fee_bps = volume_fee_rate(ctx.accounts.market.fee_bps, &ctx.accounts.user_volume);
+    let fee_amount = bet_fee(cost, fee_bps)?;
+    let total = cost
+        .checked_add(fee_amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    // Transfer tokens from user to vault
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.market_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    transfer(cpi_ctx, total)?;
+
+    accrue_bet_fee(ctx.accounts, fee_amount)?;
+
+    let market = &mut ctx.accounts.market;
+    market.pools[index] = market.pools[index]
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.collateral = market.collateral
+        .checked_add(cost)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    // Initialize or update position
+    let market_key = market.key();
+    let position = &mut ctx.accounts.position;
+    if position.user == Pubkey::default() {
+        position.user = ctx.accounts.user.key();
+        position.market = market_key;
+        position.amounts = vec![0; market.pools.len()];
+        position.fees_paid = 0;
+        position.lp_shares = 0;
+        position.exchange_paid = 0;
+        position.volume = 0;
+        position.bets = vec![];
+        position.shares = vec![];
+        position.bump = ctx.bumps.position;
+    }
+
+    position.amounts[index] = position.amounts[index]
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    position.fees_paid = position.fees_paid
+        .checked_add(fee_amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    record_volume(&mut ctx.accounts.user_volume, position, cost)?;
+
+    let prices = scaled_prices(market)?;
+    msg!(
+        "Bought {} shares of {} for {} tokens | Prices: {:?}",
+        shares,
+        market.outcome_labels[index],
+        cost,
+        prices
+    );
+
+    emit!(SharesTraded {
+        market: market_key,
+        user: ctx.accounts.user.key(),
+        outcome: outcome_index,
+        shares,
+        amount: cost,
+        is_buy: true,
+        prices,
+    });
+
+    emit!(FeeCharged {
+        market: market_key,
+        user: ctx.accounts.user.key(),
+        amount: cost,
+        fee_bps,
+        fee: fee_amount,
+    });
+
+    Ok(())
+}
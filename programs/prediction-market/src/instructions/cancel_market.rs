@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::lmsr::*;
+use crate::amm::*;
+
+pub fn void_market(ctx: Context<CancelMarket>) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedCanceller
+    );
+    require!(
+        !market.resolved,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    require!(
+        !market.voided,
+        PredictionMarketError::MarketVoided
+    );
+
+    // Fees already swept by collect_fees have to go back into the vault,
+    // otherwise claim_refund could not return every bettor's fee in full
+    let fees_withdrawn = market.fees_withdrawn;
+    if fees_withdrawn > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.authority_token_account.to_account_info(),
+            to: ctx.accounts.market_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer(cpi_ctx, fees_withdrawn)?;
+    }
+
+    let market = &mut ctx.accounts.market;
+    market.fees_collected = market.fees_collected
+        .checked_add(fees_withdrawn)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.fees_withdrawn = 0;
+    market.voided = true;
+
+    match market.pricing {
+        PricingMode::Parimutuel | PricingMode::Exchange | PricingMode::FixedOdds => {},
+        PricingMode::Lmsr { .. } => settle_at_last_prices(market)?,
+        PricingMode::ConstantProduct { .. } => settle_at_reserve_prices(market)?,
+    }
+
+    msg!(
+        "Market voided: {} vs {} | Game key: {}",
+        market.home_team,
+        market.away_team,
+        market.game_key
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ PredictionMarketError::InvalidVault,
+        constraint = authority_token_account.mint == market_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
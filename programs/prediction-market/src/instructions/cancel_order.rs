@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn cancel_resting_order(ctx: Context<CancelOrder>, order_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    let order_book = &mut ctx.accounts.order_book;
+
+    let index = order_book.orders
+        .iter()
+        .position(|order| order.order_id == order_id)
+        .ok_or(PredictionMarketError::OrderNotFound)?;
+    let order = order_book.orders[index];
+
+    // Orders can no longer match once the market starts, so from then on
+    // anyone can clear them and send the escrow back to the owner
+    let closed = clock.unix_timestamp >= market.start_time || market.resolved || market.voided;
+    require!(
+        order.owner == ctx.accounts.caller.key() || closed,
+        PredictionMarketError::UnauthorizedOrderCancel
+    );
+    require!(
+        ctx.accounts.owner_token_account.owner == order.owner,
+        PredictionMarketError::InvalidVault
+    );
+
+    let refund = order.escrow
+        .checked_add(order.fee_reserved)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let market_key = market.key();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.market_vault.to_account_info(),
+        to: ctx.accounts.owner_token_account.to_account_info(),
+        authority: ctx.accounts.market_vault.to_account_info(),
+    };
+    let bump = ctx.bumps.market_vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        market_key.as_ref(),
+        &[bump],
+    ]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    anchor_spl::token::transfer(cpi_ctx, refund)?;
+
+    order_book.orders.remove(index);
+
+    msg!("Order {} cancelled: {} tokens returned", order_id, refund);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref()],
+        bump = order_book.bump,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == market_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
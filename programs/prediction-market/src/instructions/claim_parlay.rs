@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn claim_parlay_payout(ctx: Context<ClaimParlay>) -> Result<()> {
+    let parlay_pool = &mut ctx.accounts.parlay_pool;
+    let parlay = &mut ctx.accounts.parlay;
+
+    require!(
+        parlay.status == ParlayStatus::Settled,
+        PredictionMarketError::ParlayNotSettled
+    );
+
+    let payout = parlay.payout;
+    require!(payout > 0, PredictionMarketError::NoWinningsToClaim);
+
+    // Transfer payout to user
+    let pool_key = parlay_pool.key();
+    let cpi_accounts = anchor_spl::token::Transfer {
+        from: ctx.accounts.parlay_vault.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.parlay_vault.to_account_info(),
+    };
+    let bump = ctx.bumps.parlay_vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"parlay_vault",
+        pool_key.as_ref(),
+        &[bump],
+    ]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    anchor_spl::token::transfer(cpi_ctx, payout)?;
+
+    parlay_pool.reserved = parlay_pool.reserved
+        .checked_sub(payout)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    parlay.status = ParlayStatus::Claimed;
+
+    msg!("Parlay claimed: {} tokens", payout);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimParlay<'info> {
+    #[account(
+        mut,
+        constraint = parlay.user == user.key() @ PredictionMarketError::InvalidVault,
+        constraint = parlay.pool == parlay_pool.key() @ PredictionMarketError::InvalidVault
+    )]
+    pub parlay: Account<'info, Parlay>,
+
+    #[account(mut)]
+    pub parlay_pool: Account<'info, ParlayPool>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidVault,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault", parlay_pool.key().as_ref()],
+        bump,
+    )]
+    pub parlay_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
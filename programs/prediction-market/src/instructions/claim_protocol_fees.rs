@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn collect_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        ctx.accounts.protocol_config.treasury == ctx.accounts.treasury.key(),
+        PredictionMarketError::UnauthorizedFeeCollector
+    );
+
+    // Only the creator can be made to pay fees back if the market is voided,
+    // so the treasury waits for the market to settle
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(market.resolved, PredictionMarketError::MarketNotResolved);
+
+    let fees = market.protocol_fees;
+    require!(fees > 0, PredictionMarketError::NoFeesToCollect);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.market_vault.to_account_info(),
+        to: ctx.accounts.fee_receiver.to_account_info(),
+        authority: ctx.accounts.market_vault.to_account_info(),
+    };
+
+    let market_key = market.key();
+    let vault_seeds = &[b"vault", market_key.as_ref(), &[ctx.bumps.market_vault]];
+    let signer = &[&vault_seeds[..]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    anchor_spl::token::transfer(cpi_ctx, fees)?;
+
+    market.protocol_fees = 0;
+    market.fees_collected = market.fees_collected
+        .checked_sub(fees)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    msg!("Protocol fees claimed: {} tokens", fees);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimProtocolFees<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub treasury: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = fee_receiver.owner == treasury.key() @ PredictionMarketError::InvalidVault,
+        constraint = fee_receiver.mint == market_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub fee_receiver: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
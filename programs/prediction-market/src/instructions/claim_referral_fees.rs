@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn collect_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let referral = &mut ctx.accounts.referral;
+
+    // Referred fees go back to the bettors if the market is voided
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(market.resolved, PredictionMarketError::MarketNotResolved);
+
+    let fees = referral.fees_owed;
+    require!(fees > 0, PredictionMarketError::NoFeesToCollect);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.market_vault.to_account_info(),
+        to: ctx.accounts.fee_receiver.to_account_info(),
+        authority: ctx.accounts.market_vault.to_account_info(),
+    };
+
+    let market_key = market.key();
+    let vault_seeds = &[b"vault", market_key.as_ref(), &[ctx.bumps.market_vault]];
+    let signer = &[&vault_seeds[..]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    anchor_spl::token::transfer(cpi_ctx, fees)?;
+
+    referral.fees_owed = 0;
+    market.referrer_fees = market.referrer_fees
+        .checked_sub(fees)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.fees_collected = market.fees_collected
+        .checked_sub(fees)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    msg!("Referral fees claimed: {} tokens by {}", fees, referral.referrer);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", market.key().as_ref(), referrer.key().as_ref()],
+        bump = referral.bump,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = fee_receiver.owner == referrer.key() @ PredictionMarketError::InvalidVault,
+        constraint = fee_receiver.mint == market_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub fee_receiver: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
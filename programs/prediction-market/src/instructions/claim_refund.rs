@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::place_bet::release_volume;
+use crate::payout::*;
+
+pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+
+    require!(market.voided, PredictionMarketError::MarketNotVoided);
+
+    // A voided market returns every stake plus the fee charged in place_bet,
+    // AMM shares are bought back at the prices they were voided at, matched
+    // exchange bets return what each side put in
+    let stake = match market.pricing {
+        PricingMode::Parimutuel | PricingMode::FixedOdds => total_of(&position.amounts)?,
+        PricingMode::Exchange => position.exchange_paid,
+        _ => position_payout(market, position)?,
+    };
+
+    let refund = stake
+        .checked_add(position.fees_paid)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    require!(refund > 0, PredictionMarketError::NoRefundToClaim);
+
+    // Transfer refund to user
+    let market_key = market.key();
+    let cpi_accounts = anchor_spl::token::Transfer {
+        from: ctx.accounts.market_vault.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.market_vault.to_account_info(),
+    };
+    let bump = ctx.bumps.market_vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        market_key.as_ref(),
+        &[bump],
+    ]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    anchor_spl::token::transfer(cpi_ctx, refund)?;
+
+    market.fees_collected = market.fees_collected
+        .checked_sub(position.fees_paid)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    // Reset position after refund
+    position.amounts.iter_mut().for_each(|amount| *amount = 0);
+    position.fees_paid = 0;
+    position.exchange_paid = 0;
+    position.bets.clear();
+    position.shares.iter_mut().for_each(|shares| *shares = 0);
+
+    // A refunded stake no longer counts towards the bettor's volume
+    let counted = position.volume;
+    release_volume(&mut ctx.accounts.user_volume, position, counted)?;
+
+    msg!("Refund claimed: {} tokens ({} stake + {} fees)", refund, stake, refund - stake);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        constraint = position.user == user.key() @ PredictionMarketError::InvalidVault,
+        constraint = position.market == market.key() @ PredictionMarketError::InvalidVault
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidVault,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    /// Bettor's volume account, needed if the position counted towards it
+    #[account(
+        mut,
+        seeds = [b"user_volume", user.key().as_ref()],
+        bump = user_volume.bump,
+    )]
+    pub user_volume: Option<Account<'info, UserVolume>>,
+
+    pub token_program: Program<'info, Token>,
+}
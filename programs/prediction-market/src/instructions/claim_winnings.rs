@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 use crate::state::*;
 use crate::errors::*;
+use crate::payout::*;
 
 pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
     let market = &ctx.accounts.market;
@@ -9,84 +10,12 @@ pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
 
     require!(market.resolved, PredictionMarketError::MarketNotResolved);
 
-    let mut winnings: u64 = 0;
-
-    if market.is_draw {
-        // DRAW SCENARIO
-        // Only users who bet on draw win!
-        // Users who bet on home/away LOSE (just like draw bettors lose on home/away wins)
-        
-        if position.draw_amount > 0 {
-            // User bet on draw and won!
-            require!(market.draw_pool > 0, PredictionMarketError::MathOverflow);
-            
-            let total_pool = market.yes_pool
-                .checked_add(market.no_pool)
-                .ok_or(PredictionMarketError::MathOverflow)?
-                .checked_add(market.draw_pool)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-            
-            winnings = position.draw_amount
-                .checked_mul(total_pool)
-                .ok_or(PredictionMarketError::MathOverflow)?
-                .checked_div(market.draw_pool)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-                
-            msg!("Draw bettor wins: {} tokens", winnings);
-        } else {
-            // User bet on home or away - they LOSE!
-            winnings = 0;
-            msg!("Home/Away bettor loses on draw");
-        }
-    } else if let Some(outcome) = market.outcome {
-        // HOME/AWAY WIN SCENARIO
-        
-        if outcome {
-            // HOME TEAM WON
-            if position.yes_amount > 0 {
-                require!(market.yes_pool > 0, PredictionMarketError::MathOverflow);
-                
-                let total_pool = market.yes_pool
-                    .checked_add(market.no_pool)
-                    .ok_or(PredictionMarketError::MathOverflow)?
-                    .checked_add(market.draw_pool)
-                    .ok_or(PredictionMarketError::MathOverflow)?;
-                
-                winnings = position.yes_amount
-                    .checked_mul(total_pool)
-                    .ok_or(PredictionMarketError::MathOverflow)?
-                    .checked_div(market.yes_pool)
-                    .ok_or(PredictionMarketError::MathOverflow)?;
-                    
-                msg!("Home bettor wins: {} tokens", winnings);
-            } else {
-                // User bet on away or draw, no winnings
-                winnings = 0;
-            }
-        } else {
-            // AWAY TEAM WON
-            if position.no_amount > 0 {
-                require!(market.no_pool > 0, PredictionMarketError::MathOverflow);
-                
-                let total_pool = market.yes_pool
-                    .checked_add(market.no_pool)
-                    .ok_or(PredictionMarketError::MathOverflow)?
-                    .checked_add(market.draw_pool)
-                    .ok_or(PredictionMarketError::MathOverflow)?;
-                
-                winnings = position.no_amount
-                    .checked_mul(total_pool)
-                    .ok_or(PredictionMarketError::MathOverflow)?
-                    .checked_div(market.no_pool)
-                    .ok_or(PredictionMarketError::MathOverflow)?;
-                    
-                msg!("Away bettor wins: {} tokens", winnings);
-            } else {
-                // User bet on home or draw, no winnings
-                winnings = 0;
-            }
-        }
-    }
+    // Pro-rata share of every outcome the position backed, which covers
+    // single winners as well as refund settlements
+    let winnings = match market.pricing {
+        PricingMode::FixedOdds => fixed_odds_payout(market, position)?,
+        _ => position_payout(market, position)?,
+    };
 
     require!(winnings > 0, PredictionMarketError::NoWinningsToClaim);
 
@@ -108,9 +37,9 @@ pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
     anchor_spl::token::transfer(cpi_ctx, winnings)?;
 
     // Reset position after claiming winnings
-    position.yes_amount = 0;
-    position.no_amount = 0;
-    position.draw_amount = 0;  // Don't forget this!
+    position.amounts.iter_mut().for_each(|amount| *amount = 0);
+    position.bets.clear();
+    position.shares.iter_mut().for_each(|shares| *shares = 0);
 
     msg!("Winnings claimed: {} tokens", winnings);
 
@@ -11,7 +11,17 @@ pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
         PredictionMarketError::UnauthorizedFeeCollector
     );
 
-    let fees = market.fees_collected;
+    // Fees on a voided market belong to the bettors and are returned by claim_refund
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    // A conditional market can still be voided without its authority, so its
+    // fees stay in the vault until it has resolved
+    require!(
+        market.parent_market.is_none() || market.resolved,
+        PredictionMarketError::MarketNotResolved
+    );
+
+    // The treasury and referrers claim their shares themselves
+    let fees = market.creator_fees;
     require!(fees > 0, PredictionMarketError::NoFeesToCollect);
 
     let cpi_accounts = Transfer {
@@ -27,7 +37,13 @@ pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
     anchor_spl::token::transfer(cpi_ctx, fees)?;
 
-    market.fees_collected = 0;
+    market.creator_fees = 0;
+    market.fees_collected = market.fees_collected
+        .checked_sub(fees)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.fees_withdrawn = market.fees_withdrawn
+        .checked_add(fees)
+        .ok_or(PredictionMarketError::MathOverflow)?;
 
     Ok(())
 }
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn init_amm_market(
+        ctx: Context<CreateAmmMarket>,
+        question: String,
+        market_key: String,
+        outcome_labels: Vec<String>,
+        lp_fee_bps: u16,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, PredictionMarketError::FeeTooHigh);
+        require!(
+            outcome_labels.len() >= 2 && outcome_labels.len() <= MAX_OUTCOMES,
+            PredictionMarketError::InvalidOutcomeCount
+        );
+        require!(
+            outcome_labels.iter().all(|label| label.len() <= MAX_OUTCOME_LABEL_LEN),
+            PredictionMarketError::OutcomeLabelTooLong
+        );
+        require!(lp_fee_bps <= MAX_LP_FEE_BPS, PredictionMarketError::InvalidLpFee);
+
+        let market = &mut ctx.accounts.market;
+        market.set_inner(Market {
+            pricing: PricingMode::ConstantProduct { fee_bps: lp_fee_bps },
+            reserves: vec![0; outcome_labels.len()],
+            ..Market::new(
+                ctx.accounts.authority.key(),
+                question,
+                market_key,
+                MarketType::Categorical,
+                outcome_labels,
+                start_time,
+                end_time,
+                resolution_time,
+                oracle_feed,
+                fee_bps,
+                ctx.accounts.vault.key(),
+                ctx.bumps.market,
+            )
+        });
+
+        msg!(
+        "AMM market created: {} | {} outcomes | LP fee: {} bps | Market key: {}",
+        market.question,
+        market.outcome_labels.len(),
+        lp_fee_bps,
+        market.game_key
+        );
+
+        msg!("Feed hash stored: {:?}", market.oracle_feed);
+        Ok(())
+    }
+
+#[derive(Accounts)]
+#[instruction(question: String, market_key: String)]
+pub struct CreateAmmMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", authority.key().as_ref(), market_key.as_bytes()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+}
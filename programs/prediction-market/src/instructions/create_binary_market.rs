@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn init_binary_market(
+        ctx: Context<CreateBinaryMarket>,
+        question: String,
+        market_key: String,
+        first_outcome: String,
+        second_outcome: String,
+        category: Option<String>,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, PredictionMarketError::FeeTooHigh);
+        require!(
+            first_outcome.len() <= MAX_OUTCOME_LABEL_LEN && second_outcome.len() <= MAX_OUTCOME_LABEL_LEN,
+            PredictionMarketError::OutcomeLabelTooLong
+        );
+        let category = category.unwrap_or_default();
+        require!(
+            category.len() <= MAX_CATEGORY_LEN,
+            PredictionMarketError::CategoryTooLong
+        );
+
+        // Resolves like any categorical market: the oracle reports 0 or 1
+        let market = &mut ctx.accounts.market;
+        market.set_inner(Market {
+            category,
+            ..Market::new(
+                ctx.accounts.authority.key(),
+                question,
+                market_key,
+                MarketType::Categorical,
+                vec![first_outcome, second_outcome],
+                start_time,
+                end_time,
+                resolution_time,
+                oracle_feed,
+                fee_bps,
+                ctx.accounts.vault.key(),
+                ctx.bumps.market,
+            )
+        });
+
+        msg!(
+        "Binary market created: {} | {} / {} | Category: {} | Market key: {}",
+        market.question,
+        market.outcome_labels[0],
+        market.outcome_labels[1],
+        market.category,
+        market.game_key
+        );
+
+        msg!("Feed hash stored: {:?}", market.oracle_feed);
+        Ok(())
+    }
+
+#[derive(Accounts)]
+#[instruction(question: String, market_key: String)]
+pub struct CreateBinaryMarket<'info> {
+    /// Seeded by the creator as well as the key, so teams running different
+    /// verticals on one deployment never collide on a market key
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", authority.key().as_ref(), market_key.as_bytes()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn init_conditional_market(
+        ctx: Context<CreateConditionalMarket>,
+        question: String,
+        market_key: String,
+        outcome_labels: Vec<String>,
+        parent_outcome: u8,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, PredictionMarketError::FeeTooHigh);
+        require!(
+            outcome_labels.len() >= 2 && outcome_labels.len() <= MAX_OUTCOMES,
+            PredictionMarketError::InvalidOutcomeCount
+        );
+        require!(
+            outcome_labels.iter().all(|label| label.len() <= MAX_OUTCOME_LABEL_LEN),
+            PredictionMarketError::OutcomeLabelTooLong
+        );
+
+        // The condition must still be open when the child is listed
+        let parent = &ctx.accounts.parent_market;
+        require!(
+            !parent.resolved && !parent.voided,
+            PredictionMarketError::InvalidParentMarket
+        );
+        require!(
+            (parent_outcome as usize) < parent.pools.len(),
+            PredictionMarketError::InvalidOutcome
+        );
+
+        let market = &mut ctx.accounts.market;
+        market.set_inner(Market {
+            parent_market: Some(ctx.accounts.parent_market.key()),
+            parent_outcome,
+            ..Market::new(
+                ctx.accounts.authority.key(),
+                question,
+                market_key,
+                MarketType::Categorical,
+                outcome_labels,
+                start_time,
+                end_time,
+                resolution_time,
+                oracle_feed,
+                fee_bps,
+                ctx.accounts.vault.key(),
+                ctx.bumps.market,
+            )
+        });
+
+        msg!(
+        "Conditional market created: {} | Requires {} to resolve to {} | Market key: {}",
+        market.question,
+        ctx.accounts.parent_market.key(),
+        ctx.accounts.parent_market.outcome_labels[parent_outcome as usize],
+        market.game_key
+        );
+
+        msg!("Feed hash stored: {:?}", market.oracle_feed);
+        Ok(())
+    }
+
+#[derive(Accounts)]
+#[instruction(question: String, market_key: String)]
+pub struct CreateConditionalMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", authority.key().as_ref(), market_key.as_bytes()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub parent_market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::state::*;
+use crate::errors::*;
 
 pub fn create_market(
         ctx: Context<CreateMarket>,
@@ -11,32 +12,86 @@ pub fn create_market(
         start_time: i64,
         end_time: i64,
         resolution_time: i64,
-        oracle_feed: [u8; 32]
+        oracle_feed: [u8; 32],
+        market_type: MarketType,
+        fee_bps: u16
     ) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, PredictionMarketError::FeeTooHigh);
+
+        let outcome_labels = match market_type {
+            MarketType::MatchResult => vec![
+                away_team.clone(),
+                home_team.clone(),
+                "Draw".to_string(),
+            ],
+            MarketType::TotalGoals { line } => {
+                // Whole or half goal lines only, a whole line pushes on an exact total
+                require!(
+                    line > 0 && line as i64 % (LINE_SCALE / 2) == 0,
+                    PredictionMarketError::InvalidLine
+                );
+                vec![
+                    format!("Over {}", format_line(line)),
+                    format!("Under {}", format_line(line)),
+                ]
+            },
+            MarketType::AsianHandicap { line } => {
+                // Whole, half and quarter goal lines
+                require!(
+                    line as i64 % (LINE_SCALE / 4) == 0,
+                    PredictionMarketError::InvalidLine
+                );
+                vec![
+                    format!("{} {}", home_team, format_handicap(line)),
+                    format!("{} {}", away_team, format_handicap(-line)),
+                ]
+            },
+            MarketType::CorrectScore { max_goals } => {
+                require!(
+                    (1..=MAX_CORRECT_SCORE_GOALS).contains(&max_goals),
+                    PredictionMarketError::InvalidScoreGrid
+                );
+                // Laid out so correct_score_outcome(home, away) indexes the label
+                let mut labels = Vec::new();
+                for home_goals in 0..=max_goals {
+                    for away_goals in 0..=max_goals {
+                        labels.push(format!("{}-{}", home_goals, away_goals));
+                    }
+                }
+                labels.push("Any other".to_string());
+                labels
+            },
+            MarketType::Categorical | MarketType::Scalar { .. } => {
+                return err!(PredictionMarketError::InvalidMarketType)
+            },
+        };
+
         let market = &mut ctx.accounts.market;
-        market.authority = ctx.accounts.authority.key();
-        market.question = question;
-        market.home_team = home_team;
-        market.away_team = away_team;
-        market.game_key = game_key;
-        market.start_time = start_time;
-        market.end_time = end_time;
-        market.resolution_time = resolution_time;
-        market.yes_pool = 0; 
-        market.no_pool = 0; 
-        market.draw_pool = 0; 
-        market.resolved = false;
-        market.outcome = None;
-        market.is_draw = false;
-        market.oracle_feed = oracle_feed;
-        market.vault = ctx.accounts.vault.key();
-        market.bump = ctx.bumps.market;
+        market.set_inner(Market {
+            home_team,
+            away_team,
+            ..Market::new(
+                ctx.accounts.authority.key(),
+                question,
+                game_key,
+                market_type,
+                outcome_labels,
+                start_time,
+                end_time,
+                resolution_time,
+                oracle_feed,
+                fee_bps,
+                ctx.accounts.vault.key(),
+                ctx.bumps.market,
+            )
+        });
 
         msg!(
-        "Market created: {} vs {} | Game key: {}", 
+        "Market created: {} vs {} | Game key: {} | Type: {:?}", 
         market.home_team, 
         market.away_team,
-        market.game_key
+        market.game_key,
+        market.market_type
         );
 
         msg!("Feed hash stored: {:?}", market.oracle_feed);
@@ -44,13 +99,23 @@ pub fn create_market(
     }
 
 #[derive(Accounts)]
-#[instruction(question: String, home_team: String, away_team: String, game_key: String)]
+#[instruction(
+    question: String,
+    home_team: String,
+    away_team: String,
+    game_key: String,
+    start_time: i64,
+    end_time: i64,
+    resolution_time: i64,
+    oracle_feed: [u8; 32],
+    market_type: MarketType
+)]
 pub struct CreateMarket<'info> {
     #[account(
         init,
         payer = authority,
         space = 8 + Market::INIT_SPACE,
-        seeds = [b"market", game_key.as_bytes()],
+        seeds = [b"market", game_key.as_bytes(), market_type.seed_suffix().as_ref()],
         bump,
     )]
     pub market: Account<'info, Market>,
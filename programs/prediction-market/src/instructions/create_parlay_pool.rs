@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn init_parlay_pool(
+    ctx: Context<CreateParlayPool>,
+    max_ticket_payout: u64,
+    min_leg_pool: u64,
+    margin_bps: u16,
+) -> Result<()> {
+    require!(
+        margin_bps <= MAX_PARLAY_MARGIN_BPS,
+        PredictionMarketError::InvalidParlayMargin
+    );
+
+    let parlay_pool = &mut ctx.accounts.parlay_pool;
+    parlay_pool.authority = ctx.accounts.authority.key();
+    parlay_pool.mint = ctx.accounts.mint.key();
+    parlay_pool.vault = ctx.accounts.parlay_vault.key();
+    parlay_pool.reserved = 0;
+    parlay_pool.max_ticket_payout = max_ticket_payout;
+    parlay_pool.min_leg_pool = min_leg_pool;
+    parlay_pool.margin_bps = margin_bps;
+    parlay_pool.bump = ctx.bumps.parlay_pool;
+
+    msg!(
+        "Parlay pool created for mint {} | Max ticket payout: {} | Margin: {} bps",
+        parlay_pool.mint,
+        parlay_pool.max_ticket_payout,
+        parlay_pool.margin_bps
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateParlayPool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ParlayPool::INIT_SPACE,
+        seeds = [b"parlay_pool", authority.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub parlay_pool: Account<'info, ParlayPool>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"parlay_vault", parlay_pool.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = parlay_vault
+    )]
+    pub parlay_vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+pub fn init_referral(ctx: Context<CreateReferral>) -> Result<()> {
+    let referral = &mut ctx.accounts.referral;
+    referral.market = ctx.accounts.market.key();
+    referral.referrer = ctx.accounts.referrer.key();
+    referral.fees_owed = 0;
+    referral.bump = ctx.bumps.referral;
+
+    msg!("Referral opened for {} on {}", referral.referrer, referral.market);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateReferral<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [b"referral", market.key().as_ref(), referrer.key().as_ref()],
+        bump,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn init_scalar_market(
+        ctx: Context<CreateScalarMarket>,
+        question: String,
+        market_key: String,
+        lower: i64,
+        upper: i64,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, PredictionMarketError::FeeTooHigh);
+        // Negative values are reserved for the oracle's "not finished" code
+        require!(
+            lower >= 0 && upper > lower,
+            PredictionMarketError::InvalidScalarBounds
+        );
+
+        let market = &mut ctx.accounts.market;
+        market.set_inner(Market::new(
+            ctx.accounts.authority.key(),
+            question,
+            market_key,
+            MarketType::Scalar { lower, upper },
+            vec![
+                format!("Long {}-{}", lower, upper),
+                format!("Short {}-{}", lower, upper),
+            ],
+            start_time,
+            end_time,
+            resolution_time,
+            oracle_feed,
+            fee_bps,
+            ctx.accounts.vault.key(),
+            ctx.bumps.market,
+        ));
+
+        msg!(
+        "Scalar market created: {} | Range: {} to {} | Market key: {}",
+        market.question,
+        lower,
+        upper,
+        market.game_key
+        );
+
+        msg!("Feed hash stored: {:?}", market.oracle_feed);
+        Ok(())
+    }
+
+#[derive(Accounts)]
+#[instruction(question: String, market_key: String)]
+pub struct CreateScalarMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", authority.key().as_ref(), market_key.as_bytes()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+}
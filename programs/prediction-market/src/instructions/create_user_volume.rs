@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+pub fn init_user_volume(ctx: Context<CreateUserVolume>) -> Result<()> {
+    let user_volume = &mut ctx.accounts.user_volume;
+    user_volume.user = ctx.accounts.user.key();
+    user_volume.cumulative_stake = 0;
+    user_volume.bump = ctx.bumps.user_volume;
+
+    msg!("Volume tracking opened for {}", user_volume.user);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateUserVolume<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = 8 + UserVolume::INIT_SPACE,
+        seeds = [b"user_volume", user.key().as_ref()],
+        bump,
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+pub fn challenge_proposal(ctx: Context<DisputeProposal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &ctx.accounts.proposal;
+
+    require!(
+        proposal.status == ProposalStatus::Proposed,
+        PredictionMarketError::InvalidProposalStatus
+    );
+    require!(
+        clock.unix_timestamp <= proposal.challenge_ends_at,
+        PredictionMarketError::ChallengeWindowClosed
+    );
+    require!(
+        proposal.proposer != ctx.accounts.disputer.key(),
+        PredictionMarketError::SelfDispute
+    );
+
+    // The dispute is backed by the same bond as the proposal
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.disputer_token_account.to_account_info(),
+        to: ctx.accounts.bond_vault.to_account_info(),
+        authority: ctx.accounts.disputer.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    transfer(cpi_ctx, proposal.bond)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.disputer = Some(ctx.accounts.disputer.key());
+    proposal.status = ProposalStatus::Disputed;
+
+    ctx.accounts.market.disputed = true;
+
+    msg!("Proposed result {} disputed by {}", proposal.result, ctx.accounts.disputer.key());
+
+    emit!(ProposalDisputed {
+        market: proposal.market,
+        disputer: ctx.accounts.disputer.key(),
+        result: proposal.result,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DisputeProposal<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"bond_vault", market.key().as_ref()],
+        bump,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    pub disputer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = disputer_token_account.owner == disputer.key() @ PredictionMarketError::InvalidVault,
+        constraint = disputer_token_account.mint == bond_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub disputer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
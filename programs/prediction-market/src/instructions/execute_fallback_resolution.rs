@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::resolve_market::{check_resolvable, settle_result};
+
+pub fn execute_fallback(ctx: Context<ExecuteFallbackResolution>) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedFallback
+    );
+    check_resolvable(market, ctx.accounts.parent_market.as_ref(), &clock)?;
+    require!(!market.proposal_open, PredictionMarketError::ProposalOpen);
+
+    let fallback = market.fallback.ok_or(PredictionMarketError::FallbackNotAnnounced)?;
+    require!(
+        clock.unix_timestamp >= fallback.executable_at,
+        PredictionMarketError::TimelockActive
+    );
+
+    msg!("Settling on fallback result {}", fallback.result);
+
+    settle_result(&mut ctx.accounts.market, fallback.result)
+}
+
+#[derive(Accounts)]
+pub struct ExecuteFallbackResolution<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+
+    /// Parent market, only required for a conditional market
+    pub parent_market: Option<Account<'info, Market>>,
+}
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::oracle::*;
+use super::resolve_market::{check_resolvable, settle_result};
+
+pub fn settle_proposal(ctx: Context<FinalizeProposal>, ruling: Option<i64>) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &ctx.accounts.proposal;
+    let market = &ctx.accounts.market;
+
+    require!(
+        proposal.status != ProposalStatus::Settled,
+        PredictionMarketError::InvalidProposalStatus
+    );
+    let bond = proposal.bond;
+    let disputed = proposal.status == ProposalStatus::Disputed;
+
+    // Nothing left to settle on a voided market, every bond goes back to
+    // whoever posted it
+    if market.voided {
+        pay_bonds(ctx.accounts, ctx.bumps.bond_vault, &ctx.accounts.proposer_token_account, bond)?;
+        if disputed {
+            let disputer_token_account = ctx.accounts.disputer_token_account
+                .as_ref()
+                .ok_or(PredictionMarketError::InvalidVault)?;
+            pay_bonds(ctx.accounts, ctx.bumps.bond_vault, disputer_token_account, bond)?;
+        }
+
+        ctx.accounts.proposal.status = ProposalStatus::Settled;
+        ctx.accounts.market.proposal_open = false;
+        msg!("Market voided, proposal bonds returned");
+
+        return Ok(());
+    }
+
+    let result = if disputed {
+        escalate_dispute(&ctx, ruling, &clock)?
+    } else {
+        require!(ruling.is_none(), PredictionMarketError::InvalidProposalStatus);
+        require!(
+            clock.unix_timestamp > proposal.challenge_ends_at,
+            PredictionMarketError::ChallengeWindowOpen
+        );
+        proposal.result
+    };
+
+    check_resolvable(market, ctx.accounts.parent_market.as_ref(), &clock)?;
+
+    // The side the settled result proves right takes every bond posted
+    let bonds = if disputed {
+        bond.checked_mul(2).ok_or(PredictionMarketError::MathOverflow)?
+    } else {
+        bond
+    };
+    let (winner, winner_token_account) = if result == proposal.result {
+        (proposal.proposer, &ctx.accounts.proposer_token_account)
+    } else {
+        let disputer_token_account = ctx.accounts.disputer_token_account
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidVault)?;
+        (disputer_token_account.owner, disputer_token_account)
+    };
+    pay_bonds(ctx.accounts, ctx.bumps.bond_vault, winner_token_account, bonds)?;
+
+    ctx.accounts.proposal.status = ProposalStatus::Settled;
+    let market = &mut ctx.accounts.market;
+    market.proposal_open = false;
+    settle_result(market, result)?;
+
+    msg!("Proposal settled on {}, {} bond tokens to {}", result, bonds, winner);
+
+    emit!(ProposalSettled {
+        market: market.key(),
+        result,
+        winner,
+        bonds,
+    });
+
+    Ok(())
+}
+
+/// Result of a disputed proposal, ruled by the market's arbiter or else read
+/// from its Switchboard feed
+fn escalate_dispute(ctx: &Context<FinalizeProposal>, ruling: Option<i64>, clock: &Clock) -> Result<i64> {
+    let market = &ctx.accounts.market;
+    let OracleSource::Optimistic { arbiter, .. } = market.oracle_source else {
+        return err!(PredictionMarketError::InvalidOracleSource);
+    };
+
+    match ruling {
+        Some(ruling) => {
+            let signer = ctx.accounts.arbiter
+                .as_ref()
+                .ok_or(PredictionMarketError::UnauthorizedArbiter)?;
+            require_keys_eq!(
+                signer.key(),
+                arbiter,
+                PredictionMarketError::UnauthorizedArbiter
+            );
+            msg!("Arbiter ruled {}", ruling);
+
+            Ok(ruling)
+        },
+        None => {
+            let feed = ctx.accounts.oracle_feed
+                .as_ref()
+                .ok_or(PredictionMarketError::InvalidOracleSource)?;
+
+            SwitchboardResolver { feed }.read_result(market, clock)
+        },
+    }
+}
+
+fn pay_bonds<'info>(
+    accounts: &FinalizeProposal<'info>,
+    vault_bump: u8,
+    to: &Account<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: accounts.bond_vault.to_account_info(),
+        to: to.to_account_info(),
+        authority: accounts.bond_vault.to_account_info(),
+    };
+
+    let market_key = accounts.market.key();
+    let vault_seeds = &[b"bond_vault", market_key.as_ref(), &[vault_bump]];
+    let signer = &[&vault_seeds[..]];
+    let cpi_program = accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    transfer(cpi_ctx, amount)
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"bond_vault", market.key().as_ref()],
+        bump,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = proposer_token_account.owner == proposal.proposer @ PredictionMarketError::InvalidVault,
+        constraint = proposer_token_account.mint == bond_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+
+    /// Only required once the proposal has been disputed
+    #[account(
+        mut,
+        constraint = Some(disputer_token_account.owner) == proposal.disputer @ PredictionMarketError::InvalidVault,
+        constraint = disputer_token_account.mint == bond_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub disputer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Switchboard On-Demand pull feed a dispute escalates to,
+    /// verified against the market's feed hash
+    pub oracle_feed: Option<AccountInfo<'info>>,
+
+    /// Arbiter named by the market, rules on a dispute instead of the feed
+    pub arbiter: Option<Signer<'info>>,
+
+    /// Parent market, only required when settling a conditional market
+    pub parent_market: Option<Account<'info, Market>>,
+
+    pub token_program: Program<'info, Token>,
+}
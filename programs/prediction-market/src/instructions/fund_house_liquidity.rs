@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn fund_house(ctx: Context<FundHouseLiquidity>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedBookmaker
+    );
+    require!(
+        market.pricing == PricingMode::FixedOdds,
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.authority_token_account.to_account_info(),
+        to: ctx.accounts.market_vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    transfer(cpi_ctx, amount)?;
+
+    let market = &mut ctx.accounts.market;
+    market.collateral = market.collateral
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    msg!("House liquidity funded: {} tokens | Total: {}", amount, market.collateral);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FundHouseLiquidity<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ PredictionMarketError::InvalidVault,
+        constraint = authority_token_account.mint == market_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        constraint = market_vault.key() == market.vault @ PredictionMarketError::InvalidVault
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::program::PredictionMarket;
+
+/// Only the program's upgrade authority can set up the protocol config, it
+/// becomes the protocol admin
+pub fn init_protocol_config(
+    ctx: Context<InitializeProtocolConfig>,
+    treasury: Pubkey,
+    protocol_share_bps: u16,
+    referrer_share_bps: u16,
+) -> Result<()> {
+    require!(
+        protocol_share_bps as u64 + referrer_share_bps as u64 <= BASIS_POINT_DIVIDER,
+        PredictionMarketError::InvalidFeeSplit
+    );
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.admin = ctx.accounts.admin.key();
+    config.treasury = treasury;
+    config.protocol_share_bps = protocol_share_bps;
+    config.referrer_share_bps = referrer_share_bps;
+    config.bump = ctx.bumps.protocol_config;
+
+    msg!(
+        "Protocol config initialized: treasury {} takes {} bps, referrers {} bps",
+        treasury,
+        protocol_share_bps,
+        referrer_share_bps
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProtocolConfig::INIT_SPACE,
+        seeds = [b"protocol_config"],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ PredictionMarketError::InvalidProgramData
+    )]
+    pub program: Program<'info, PredictionMarket>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ PredictionMarketError::UnauthorizedProtocolAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
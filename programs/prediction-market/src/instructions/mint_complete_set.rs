@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Transfer, transfer};
+use crate::instructions::place_bet::{PlaceBet, accrue_bet_fee, volume_fee_rate, record_volume};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::payout::*;
+
+pub fn mint_outcome_set(ctx: Context<PlaceBet>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(
+        clock.unix_timestamp < market.betting_closes_at(),
+        PredictionMarketError::BettingClosed
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(
+        matches!(market.pricing, PricingMode::ConstantProduct { .. }),
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    // The protocol fee is charged on entry, swaps only pay the LP fee
+    let fee_bps = volume_fee_rate(ctx.accounts.market.fee_bps, &ctx.accounts.user_volume);
+    let fee_amount = bet_fee(amount, fee_bps)?;
+    let total = amount
+        .checked_add(fee_amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.market_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    transfer(cpi_ctx, total)?;
+
+    accrue_bet_fee(ctx.accounts, fee_amount)?;
+
+    let market = &mut ctx.accounts.market;
+    for pool in market.pools.iter_mut() {
+        *pool = pool
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    market.collateral = market.collateral
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let market_key = market.key();
+    let position = &mut ctx.accounts.position;
+    if position.user == Pubkey::default() {
+        position.user = ctx.accounts.user.key();
+        position.market = market_key;
+        position.amounts = vec![0; market.pools.len()];
+        position.fees_paid = 0;
+        position.lp_shares = 0;
+        position.exchange_paid = 0;
+        position.volume = 0;
+        position.bets = vec![];
+        position.shares = vec![];
+        position.bump = ctx.bumps.position;
+    }
+
+    for held in position.amounts.iter_mut() {
+        *held = held
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    position.fees_paid = position.fees_paid
+        .checked_add(fee_amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    record_volume(&mut ctx.accounts.user_volume, position, amount)?;
+
+    msg!("Minted {} complete sets of outcome shares | Fee: {} bps", amount, fee_bps);
+
+    emit!(FeeCharged {
+        market: market_key,
+        user: ctx.accounts.user.key(),
+        amount,
+        fee_bps,
+        fee: fee_amount,
+    });
+
+    Ok(())
+}
@@ -1,11 +1,109 @@
+pub mod add_liquidity;
+pub mod announce_fallback_resolution;
+pub mod buy_shares;
+pub mod cancel_market;
+pub mod cancel_order;
+pub mod claim_parlay;
+pub mod claim_protocol_fees;
+pub mod claim_referral_fees;
+pub mod claim_refund;
 pub mod claim_winnings;
 pub mod collect_fees;
+pub mod create_amm_market;
+pub mod create_binary_market;
+pub mod create_categorical_market;
+pub mod create_conditional_market;
+pub mod create_exchange_market;
+pub mod create_fixed_odds_market;
+pub mod create_lmsr_market;
 pub mod create_market;
+pub mod create_parlay_pool;
+pub mod create_referral;
+pub mod create_scalar_market;
+pub mod create_user_volume;
+pub mod dispute_proposal;
+pub mod execute_fallback_resolution;
+pub mod finalize_proposal;
+pub mod fund_house_liquidity;
+pub mod initialize_protocol_config;
+pub mod mint_complete_set;
+pub mod open_parlay;
 pub mod place_bet;
+pub mod place_order;
+pub mod propose_result;
+pub mod quote_bet;
+pub mod register_oracle_signer;
+pub mod remove_liquidity;
 pub mod resolve_market;
+pub mod revoke_oracle_signer;
+pub mod sell_shares;
+pub mod settle_parlay_leg;
+pub mod swap_outcomes;
+pub mod update_betting_cutoff;
+pub mod update_fallback_grace_period;
+pub mod update_fee_curve;
+pub mod update_feed_checks;
+pub mod update_fixed_odds;
+pub mod update_oracle_quorum;
+pub mod update_oracle_source;
+pub mod update_protocol_config;
+pub mod update_time_weighting;
+pub mod void_conditional_market;
+pub mod withdraw_house_liquidity;
+pub mod withdraw_lmsr_liquidity;
+pub mod withdraw_parlay_liquidity;
 
+pub use add_liquidity::*;
+pub use announce_fallback_resolution::*;
+pub use buy_shares::*;
+pub use cancel_market::*;
+pub use cancel_order::*;
+pub use claim_parlay::*;
+pub use claim_protocol_fees::*;
+pub use claim_referral_fees::*;
+pub use claim_refund::*;
 pub use claim_winnings::*;
 pub use collect_fees::*;
+pub use create_amm_market::*;
+pub use create_binary_market::*;
+pub use create_categorical_market::*;
+pub use create_conditional_market::*;
+pub use create_exchange_market::*;
+pub use create_fixed_odds_market::*;
+pub use create_lmsr_market::*;
 pub use create_market::*;
+pub use create_parlay_pool::*;
+pub use create_referral::*;
+pub use create_scalar_market::*;
+pub use create_user_volume::*;
+pub use dispute_proposal::*;
+pub use execute_fallback_resolution::*;
+pub use finalize_proposal::*;
+pub use fund_house_liquidity::*;
+pub use initialize_protocol_config::*;
+pub use mint_complete_set::*;
+pub use open_parlay::*;
 pub use place_bet::*;
-pub use resolve_market::*;
\ No newline at end of file
+pub use place_order::*;
+pub use propose_result::*;
+pub use quote_bet::*;
+pub use register_oracle_signer::*;
+pub use remove_liquidity::*;
+pub use resolve_market::*;
+pub use revoke_oracle_signer::*;
+pub use sell_shares::*;
+pub use settle_parlay_leg::*;
+pub use swap_outcomes::*;
+pub use update_betting_cutoff::*;
+pub use update_fallback_grace_period::*;
+pub use update_fee_curve::*;
+pub use update_feed_checks::*;
+pub use update_fixed_odds::*;
+pub use update_oracle_quorum::*;
+pub use update_oracle_source::*;
+pub use update_protocol_config::*;
+pub use update_time_weighting::*;
+pub use void_conditional_market::*;
+pub use withdraw_house_liquidity::*;
+pub use withdraw_lmsr_liquidity::*;
+pub use withdraw_parlay_liquidity::*;
\ No newline at end of file
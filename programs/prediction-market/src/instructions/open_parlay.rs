@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::*;
+
+/// Leg markets are passed as remaining accounts, one per entry in `outcomes`
+pub fn open_parlay_ticket<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenParlay<'info>>,
+    parlay_id: u64,
+    stake: u64,
+    outcomes: Vec<u8>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(stake > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        outcomes.len() >= 2
+            && outcomes.len() <= MAX_PARLAY_LEGS
+            && outcomes.len() == ctx.remaining_accounts.len(),
+        PredictionMarketError::InvalidParlayLegs
+    );
+
+    // Lock in the odds of every leg at the current pools, less the margin
+    let mut legs: Vec<ParlayLeg> = Vec::with_capacity(outcomes.len());
+    let mut max_payout = stake;
+    for (market_info, outcome) in ctx.remaining_accounts.iter().zip(outcomes.iter()) {
+        let market = Account::<Market>::try_from(market_info)?;
+
+        require!(
+            clock.unix_timestamp < market.start_time,
+            PredictionMarketError::MarketAlreadyStarted
+        );
+        require!(
+            clock.unix_timestamp < market.betting_closes_at(),
+            PredictionMarketError::BettingClosed
+        );
+        require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+        require!(!market.voided, PredictionMarketError::MarketVoided);
+        // Legs are priced off parimutuel pools
+        require!(
+            market.pricing == PricingMode::Parimutuel,
+            PredictionMarketError::UnsupportedPricingMode
+        );
+        require!(
+            (*outcome as usize) < market.pools.len(),
+            PredictionMarketError::InvalidOutcome
+        );
+        require!(
+            legs.iter().all(|leg| leg.market != market.key()),
+            PredictionMarketError::InvalidParlayLegs
+        );
+
+        let odds = parlay_leg_odds(&market, *outcome as usize, &ctx.accounts.parlay_pool)?;
+        max_payout = apply_odds(max_payout, odds)?;
+
+        legs.push(ParlayLeg {
+            market: market.key(),
+            outcome: *outcome,
+            odds,
+            status: LegStatus::Pending,
+            factor: 0,
+        });
+    }
+
+    // The pool must be able to cover every winning ticket at once
+    let parlay_pool = &ctx.accounts.parlay_pool;
+    let available = ctx.accounts.parlay_vault.amount
+        .checked_add(stake)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_sub(parlay_pool.reserved)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    require!(
+        max_payout <= parlay_pool.max_ticket_payout && max_payout <= available,
+        PredictionMarketError::InsufficientParlayLiquidity
+    );
+
+    // Escrow the stake in the parlay vault
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.parlay_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    transfer(cpi_ctx, stake)?;
+
+    let parlay_pool = &mut ctx.accounts.parlay_pool;
+    parlay_pool.reserved = parlay_pool.reserved
+        .checked_add(max_payout)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let parlay = &mut ctx.accounts.parlay;
+    parlay.user = ctx.accounts.user.key();
+    parlay.pool = parlay_pool.key();
+    parlay.parlay_id = parlay_id;
+    parlay.stake = stake;
+    parlay.max_payout = max_payout;
+    parlay.payout = stake;
+    parlay.legs = legs;
+    parlay.status = ParlayStatus::Open;
+    parlay.bump = ctx.bumps.parlay;
+
+    msg!(
+        "Parlay opened: {} legs | Stake: {} | Max payout: {}",
+        parlay.legs.len(),
+        stake,
+        max_payout
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(parlay_id: u64)]
+pub struct OpenParlay<'info> {
+    #[account(mut)]
+    pub parlay_pool: Account<'info, ParlayPool>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Parlay::INIT_SPACE,
+        seeds = [b"parlay", parlay_pool.key().as_ref(), user.key().as_ref(), &parlay_id.to_le_bytes()],
+        bump
+    )]
+    pub parlay: Account<'info, Parlay>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault", parlay_pool.key().as_ref()],
+        bump,
+        constraint = parlay_vault.key() == parlay_pool.vault @ PredictionMarketError::InvalidVault
+    )]
+    pub parlay_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
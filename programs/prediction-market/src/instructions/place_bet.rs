@@ -2,28 +2,70 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::payout::*;
 
 
-pub fn place_bet(ctx: Context<PlaceBet>, amount: u64, bet_type: BetType) -> Result<()> {
+pub fn place_match_result_bet(
+    ctx: Context<PlaceBet>,
+    amount: u64,
+    bet_type: BetType,
+    min_implied_payout_bps: Option<u64>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.market.market_type == MarketType::MatchResult,
+        PredictionMarketError::InvalidOutcome
+    );
+
+    place_bet(ctx, amount, bet_type.outcome_index(), min_implied_payout_bps)
+}
+
+/// `min_implied_payout_bps` rejects the bet if the payout multiple it would
+/// get with the pools as they stand after it is below the bettor's limit
+pub fn place_bet(
+    ctx: Context<PlaceBet>,
+    amount: u64,
+    outcome_index: u8,
+    min_implied_payout_bps: Option<u64>,
+) -> Result<()> {
     let clock = Clock::get()?;
     require!(
         clock.unix_timestamp < ctx.accounts.market.start_time, 
         PredictionMarketError::MarketAlreadyStarted
     );
+    require!(
+        clock.unix_timestamp < ctx.accounts.market.betting_closes_at(),
+        PredictionMarketError::BettingClosed
+    );
     require!(
         !ctx.accounts.market.resolved, 
         PredictionMarketError::MarketAlreadyResolved
     );
+    require!(
+        !ctx.accounts.market.voided,
+        PredictionMarketError::MarketVoided
+    );
+    require!(
+        matches!(ctx.accounts.market.pricing, PricingMode::Parimutuel | PricingMode::FixedOdds),
+        PredictionMarketError::UnsupportedPricingMode
+    );
     require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        (outcome_index as usize) < ctx.accounts.market.pools.len(),
+        PredictionMarketError::InvalidOutcome
+    );
 
-    const FEE_BASIS_POINTS: u64 = 50; // 0.5%
-    const BASIS_POINT_DIVIDER: u64 = 10_000;
+    if let Some(min_payout_bps) = min_implied_payout_bps {
+        require!(
+            projected_payout_bps(&ctx.accounts.market, outcome_index as usize, amount, clock.unix_timestamp)?
+                >= min_payout_bps,
+            PredictionMarketError::PayoutBelowLimit
+        );
+    }
 
-    let fee_amount = amount
-        .checked_mul(FEE_BASIS_POINTS)
-        .ok_or(PredictionMarketError::MathOverflow)?
-        .checked_div(BASIS_POINT_DIVIDER)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+    let curve_bps = curve_fee_bps(&ctx.accounts.market, outcome_index as usize, amount)?;
+    let fee_bps = volume_fee_rate(curve_bps, &ctx.accounts.user_volume);
+    let fee_amount = bet_fee(amount, fee_bps)?;
 
     let amount_after_fee = amount
         .checked_add(fee_amount)
@@ -39,29 +81,47 @@ pub fn place_bet(ctx: Context<PlaceBet>, amount: u64, bet_type: BetType) -> Resu
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     transfer(cpi_ctx, amount_after_fee)?;
 
-    // Update market pools based on bet type
+    accrue_bet_fee(ctx.accounts, fee_amount)?;
+
+    // Update the pool of the chosen outcome
     let market = &mut ctx.accounts.market;
-    match bet_type {
-        BetType::Home => {
-            market.yes_pool = market.yes_pool
-                .checked_add(amount)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-        },
-        BetType::Away => {
-            market.no_pool = market.no_pool
-                .checked_add(amount)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-        },
-        BetType::Draw => {
-            market.draw_pool = market.draw_pool
-                .checked_add(amount)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-        },
+    let index = outcome_index as usize;
+    market.pools[index] = market.pools[index]
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    // Earlier stakes earn more of the pot on a time-weighted market
+    let shares = bet_shares(market, amount, clock.unix_timestamp)?;
+    if market.time_weighting.is_some() {
+        market.shares[index] = market.shares[index]
+            .checked_add(shares)
+            .ok_or(PredictionMarketError::MathOverflow)?;
     }
 
-    market.fees_collected = market.fees_collected
-        .checked_add(fee_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+    // Fixed-odds bets lock in the published odds and must stay within what
+    // the house is willing and able to pay
+    let locked_odds = if market.pricing == PricingMode::FixedOdds {
+        let odds = market.fixed_odds[index];
+        market.liabilities[index] = market.liabilities[index]
+            .checked_add(apply_odds(amount, odds)?)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.collateral = market.collateral
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        let exposure = market.liabilities[index].saturating_sub(total_of(&market.pools)?);
+        require!(
+            exposure <= market.exposure_caps[index],
+            PredictionMarketError::ExposureCapExceeded
+        );
+        require!(
+            market.liabilities[index] <= market.collateral,
+            PredictionMarketError::InsufficientHouseLiquidity
+        );
+        Some(odds)
+    } else {
+        None
+    };
 
     // Initialize or update position
     let market_key = market.key();
@@ -69,39 +129,133 @@ pub fn place_bet(ctx: Context<PlaceBet>, amount: u64, bet_type: BetType) -> Resu
     if position.user == Pubkey::default() {
         position.user = ctx.accounts.user.key();
         position.market = market_key;
-        position.yes_amount = 0;
-        position.no_amount = 0;
-        position.draw_amount = 0;
+        position.amounts = vec![0; market.pools.len()];
+        position.fees_paid = 0;
+        position.lp_shares = 0;
+        position.exchange_paid = 0;
+        position.volume = 0;
+        position.bets = vec![];
+        position.shares = vec![0; market.pools.len()];
         position.bump = ctx.bumps.position;
     }
 
-    // Update position based on bet type
-    match bet_type {
-        BetType::Home => {
-            position.yes_amount = position.yes_amount
-                .checked_add(amount)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-        },
-        BetType::Away => {
-            position.no_amount = position.no_amount
-                .checked_add(amount)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-        },
-        BetType::Draw => {
-            position.draw_amount = position.draw_amount
-                .checked_add(amount)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-        },
+    position.amounts[index] = position.amounts[index]
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    if market.time_weighting.is_some() {
+        position.shares[index] = position.shares[index]
+            .checked_add(shares)
+            .ok_or(PredictionMarketError::MathOverflow)?;
     }
 
+    if let Some(odds) = locked_odds {
+        require!(
+            position.bets.len() < MAX_FIXED_ODDS_BETS,
+            PredictionMarketError::TooManyBets
+        );
+        position.bets.push(LockedBet {
+            outcome: outcome_index,
+            stake: amount,
+            odds,
+        });
+    }
+
+    // Track the fee so it can be returned if the market is voided
+    position.fees_paid = position.fees_paid
+        .checked_add(fee_amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    record_volume(&mut ctx.accounts.user_volume, position, amount)?;
+
     msg!(
-        "Bet placed: {} tokens on {:?} for {} vs {}",
+        "Bet placed: {} tokens on outcome {} ({}) | Fee: {} bps | {}",
         amount,
-        bet_type,
-        market.home_team,
-        market.away_team
+        outcome_index,
+        market.outcome_labels[index],
+        fee_bps,
+        market.question
     );
 
+    emit!(FeeCharged {
+        market: market_key,
+        user: ctx.accounts.user.key(),
+        amount,
+        fee_bps,
+        fee: fee_amount,
+    });
+
+    Ok(())
+}
+
+/// Split a fee charged through `PlaceBet`, crediting the referrer if the
+/// bettor passed a referral
+pub fn accrue_bet_fee(accounts: &mut PlaceBet, fee: u64) -> Result<()> {
+    let referrer_fee = accrue_fee(
+        &mut accounts.market,
+        &accounts.protocol_config,
+        fee,
+        accounts.referral.is_some(),
+    )?;
+
+    if let Some(referral) = accounts.referral.as_mut() {
+        require!(
+            referral.referrer != accounts.user.key(),
+            PredictionMarketError::SelfReferral
+        );
+        referral.fees_owed = referral.fees_owed
+            .checked_add(referrer_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+
+    Ok(())
+}
+
+/// Fee rate the bettor pays at their volume tier before this stake. Without a
+/// volume account the full rate applies.
+pub fn volume_fee_rate(market_fee_bps: u16, user_volume: &Option<Account<'_, UserVolume>>) -> u16 {
+    fee_rate_for(
+        market_fee_bps,
+        user_volume.as_ref().map(|user_volume| user_volume.cumulative_stake),
+    )
+}
+
+/// Add a stake to the bettor's cumulative volume, if they track it. The
+/// position remembers how much it counted so a refund or sale can take it back.
+pub fn record_volume(
+    user_volume: &mut Option<Account<'_, UserVolume>>,
+    position: &mut Position,
+    amount: u64,
+) -> Result<()> {
+    if let Some(user_volume) = user_volume.as_mut() {
+        user_volume.cumulative_stake = user_volume.cumulative_stake
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        position.volume = position.volume
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+
+    Ok(())
+}
+
+/// Take up to `amount` of the volume a position counted back out of the
+/// bettor's volume when the stake behind it is returned
+pub fn release_volume(
+    user_volume: &mut Option<Account<'_, UserVolume>>,
+    position: &mut Position,
+    amount: u64,
+) -> Result<()> {
+    let released = amount.min(position.volume);
+    if released == 0 {
+        return Ok(());
+    }
+
+    let user_volume = user_volume
+        .as_mut()
+        .ok_or(PredictionMarketError::MissingUserVolume)?;
+    user_volume.cumulative_stake = user_volume.cumulative_stake.saturating_sub(released);
+    position.volume -= released;
+
     Ok(())
 }
 
@@ -133,6 +287,28 @@ pub struct PlaceBet<'info> {
     )]
     pub market_vault: Account<'info, TokenAccount>,
 
+    /// Bettor's volume account, only needed for volume fee tiers
+    #[account(
+        mut,
+        seeds = [b"user_volume", user.key().as_ref()],
+        bump = user_volume.bump,
+    )]
+    pub user_volume: Option<Account<'info, UserVolume>>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Referral of whoever sent the bettor to this market, if anyone
+    #[account(
+        mut,
+        seeds = [b"referral", market.key().as_ref(), referral.referrer.as_ref()],
+        bump = referral.bump,
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
\ No newline at end of file
@@ -0,0 +1,340 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::payout::*;
+use crate::instructions::place_bet::record_volume;
+
+/// Positions of the resting orders' owners are passed as remaining accounts in
+/// the order they get matched: best odds first, oldest first at equal odds.
+/// Each position is followed by its owner's volume account address, which
+/// does not have to exist yet. The order fails if the accounts run out while
+/// a crossing order is left, the book never holds a back and a lay that could
+/// be matched.
+pub fn place_limit_order<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceOrder<'info>>,
+    side: OrderSide,
+    outcome: u8,
+    odds: u64,
+    stake: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(
+        clock.unix_timestamp < market.betting_closes_at(),
+        PredictionMarketError::BettingClosed
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(
+        market.pricing == PricingMode::Exchange,
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    require!(stake >= MIN_ORDER_STAKE, PredictionMarketError::OrderTooSmall);
+    require!(
+        (outcome as usize) < market.pools.len(),
+        PredictionMarketError::InvalidOutcome
+    );
+    require!(
+        odds > ODDS_SCALE && odds <= MAX_EXCHANGE_ODDS,
+        PredictionMarketError::InvalidOdds
+    );
+
+    let market_key = market.key();
+    let user_key = ctx.accounts.user.key();
+    let position = &mut ctx.accounts.position;
+    if position.user == Pubkey::default() {
+        position.user = user_key;
+        position.market = market_key;
+        position.amounts = vec![0; market.pools.len()];
+        position.fees_paid = 0;
+        position.lp_shares = 0;
+        position.exchange_paid = 0;
+        position.volume = 0;
+        position.bets = vec![];
+        position.shares = vec![];
+        position.bump = ctx.bumps.position;
+    }
+
+    // The rate is fixed at placement for the whole order, a resting order
+    // holds its fee at that rate until it fills
+    let fee_bps = fee_rate_for(
+        market.fee_bps,
+        ctx.accounts.user_volume.as_ref().map(|user_volume| user_volume.cumulative_stake),
+    );
+
+    let market = &mut ctx.accounts.market;
+    let order_book = &mut ctx.accounts.order_book;
+    let mut makers = ctx.remaining_accounts.iter();
+    let mut remaining = stake;
+    let mut taker_paid: u64 = 0;
+    let mut taker_fees: u64 = 0;
+
+    let crosses = |order: &Order| {
+        order.outcome == outcome && match side {
+            OrderSide::Back => order.side == OrderSide::Lay && order.odds >= odds,
+            OrderSide::Lay => order.side == OrderSide::Back && order.odds <= odds,
+        }
+    };
+
+    while remaining > 0 {
+        // Best crossing order on the other side, never one of the taker's own
+        let best = order_book.orders
+            .iter()
+            .enumerate()
+            .filter(|(_, order)| crosses(order) && order.owner != user_key)
+            .min_by_key(|(_, order)| match side {
+                OrderSide::Back => (u64::MAX - order.odds, order.order_id),
+                OrderSide::Lay => (order.odds, order.order_id),
+            })
+            .map(|(index, _)| index);
+
+        let Some(index) = best else { break };
+        let (Some(maker_info), Some(maker_volume_info)) = (makers.next(), makers.next()) else {
+            break;
+        };
+
+        let order = order_book.orders[index];
+        let mut maker = Account::<Position>::try_from(maker_info)?;
+        require!(
+            maker.user == order.owner && maker.market == market_key,
+            PredictionMarketError::InvalidVault
+        );
+        let (maker_volume_key, _) = Pubkey::find_program_address(
+            &[b"user_volume", order.owner.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            maker_volume_info.key(),
+            maker_volume_key,
+            PredictionMarketError::InvalidVault
+        );
+        let mut maker_volume = if maker_volume_info.data_is_empty() {
+            None
+        } else {
+            Some(Account::<UserVolume>::try_from(maker_volume_info)?)
+        };
+
+        // Fill at the resting order's odds, the maker's escrow and fee are
+        // used up in proportion so a full fill consumes them exactly
+        let fill = remaining.min(order.stake);
+        let maker_paid = (order.escrow as u128 * fill as u128 / order.stake as u128) as u64;
+        let maker_fee = (order.fee_reserved as u128 * fill as u128 / order.stake as u128) as u64;
+        let shares = match side {
+            OrderSide::Back => fill
+                .checked_add(maker_paid)
+                .ok_or(PredictionMarketError::MathOverflow)?,
+            OrderSide::Lay => apply_odds(fill, order.odds)?,
+        };
+        let paid = shares - maker_paid;
+        let fee = bet_fee(paid, fee_bps)?;
+
+        let resting = &mut order_book.orders[index];
+        resting.stake -= fill;
+        resting.escrow -= maker_paid;
+        resting.fee_reserved -= maker_fee;
+        if resting.stake == 0 {
+            order_book.orders.remove(index);
+        }
+
+        credit_shares(&mut maker, order.side, outcome, shares)?;
+        maker.exchange_paid = maker.exchange_paid
+            .checked_add(maker_paid)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        maker.fees_paid = maker.fees_paid
+            .checked_add(maker_fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        // The maker's matched stake counts towards their volume like the taker's
+        record_volume(&mut maker_volume, &mut maker, maker_paid)?;
+        maker.exit(&crate::ID)?;
+        if let Some(maker_volume) = maker_volume {
+            maker_volume.exit(&crate::ID)?;
+        }
+
+        credit_shares(position, side, outcome, shares)?;
+
+        for pool in market.pools.iter_mut() {
+            *pool = pool
+                .checked_add(shares)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+        }
+        market.collateral = market.collateral
+            .checked_add(shares)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        accrue_fee(market, &ctx.accounts.protocol_config, maker_fee + fee, false)?;
+
+        taker_paid = taker_paid
+            .checked_add(paid)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        taker_fees = taker_fees
+            .checked_add(fee)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        remaining -= fill;
+
+        let (backer, layer) = match side {
+            OrderSide::Back => (user_key, order.owner),
+            OrderSide::Lay => (order.owner, user_key),
+        };
+        emit!(OrderMatched {
+            market: market_key,
+            outcome,
+            odds: order.odds,
+            backer,
+            layer,
+            back_stake: fill,
+            lay_liability: shares - fill,
+        });
+    }
+
+    position.exchange_paid = position.exchange_paid
+        .checked_add(taker_paid)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    position.fees_paid = position.fees_paid
+        .checked_add(taker_fees)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    // Only matched stakes count towards the bettor's volume, a resting order
+    // can still be cancelled for its escrow
+    record_volume(&mut ctx.accounts.user_volume, position, taker_paid)?;
+
+    // Whatever did not match rests on the book with its escrow and fee
+    let mut resting_total: u64 = 0;
+    if remaining > 0 {
+        // Left over only when maker accounts are missing or the taker's own
+        // order is in the way
+        require!(
+            !order_book.orders.iter().any(crosses),
+            PredictionMarketError::OrderWouldCross
+        );
+        require!(
+            order_book.orders.len() < MAX_RESTING_ORDERS,
+            PredictionMarketError::OrderBookFull
+        );
+        require!(
+            order_book.orders.iter().filter(|order| order.owner == user_key).count()
+                < MAX_ORDERS_PER_OWNER,
+            PredictionMarketError::TooManyOrders
+        );
+
+        let escrow = match side {
+            OrderSide::Back => remaining,
+            OrderSide::Lay => apply_odds(remaining, odds)? - remaining,
+        };
+        let fee_reserved = bet_fee(escrow, fee_bps)?;
+        resting_total = escrow
+            .checked_add(fee_reserved)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+
+        let order_id = order_book.next_order_id;
+        order_book.orders.push(Order {
+            order_id,
+            owner: user_key,
+            side,
+            outcome,
+            odds,
+            stake: remaining,
+            escrow,
+            fee_reserved,
+        });
+        order_book.next_order_id += 1;
+    }
+
+    let total = taker_paid
+        .checked_add(taker_fees)
+        .and_then(|total| total.checked_add(resting_total))
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    // Escrow everything in the market vault
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.market_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    transfer(cpi_ctx, total)?;
+
+    msg!(
+        "{:?} order on {} at {} | Matched {} of {} | Resting {}",
+        side,
+        market.outcome_labels[outcome as usize],
+        odds,
+        stake - remaining,
+        stake,
+        remaining
+    );
+
+    Ok(())
+}
+
+/// A matched bet mints `shares` complete sets: the backer takes the outcome,
+/// the layer takes every other outcome
+fn credit_shares(position: &mut Position, side: OrderSide, outcome: u8, shares: u64) -> Result<()> {
+    for (index, amount) in position.amounts.iter_mut().enumerate() {
+        let is_outcome = index == outcome as usize;
+        if is_outcome == (side == OrderSide::Back) {
+            *amount = amount
+                .checked_add(shares)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref()],
+        bump = order_book.bump,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        constraint = market_vault.key() == market.vault @ PredictionMarketError::InvalidVault
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    /// Bettor's volume account, only needed for volume fee tiers
+    #[account(
+        mut,
+        seeds = [b"user_volume", user.key().as_ref()],
+        bump = user_volume.bump,
+    )]
+    pub user_volume: Option<Account<'info, UserVolume>>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
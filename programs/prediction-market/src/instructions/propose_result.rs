@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::resolve_market::{check_resolvable, check_result};
+
+pub fn open_proposal(ctx: Context<ProposeResult>, result: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+
+    let OracleSource::Optimistic { bond, challenge_seconds, .. } = market.oracle_source else {
+        return err!(PredictionMarketError::InvalidOracleSource);
+    };
+    check_resolvable(market, ctx.accounts.parent_market.as_ref(), &clock)?;
+    check_result(market, result)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.proposer_token_account.to_account_info(),
+        to: ctx.accounts.bond_vault.to_account_info(),
+        authority: ctx.accounts.proposer.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    transfer(cpi_ctx, bond)?;
+
+    let challenge_ends_at = clock.unix_timestamp
+        .checked_add(challenge_seconds)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.market = ctx.accounts.market.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.result = result;
+    proposal.bond = bond;
+    proposal.challenge_ends_at = challenge_ends_at;
+    proposal.disputer = None;
+    proposal.status = ProposalStatus::Proposed;
+    proposal.bump = ctx.bumps.proposal;
+
+    ctx.accounts.market.proposal_open = true;
+
+    msg!("Result {} proposed, open to dispute until {}", result, challenge_ends_at);
+
+    emit!(ResultProposed {
+        market: proposal.market,
+        proposer: proposal.proposer,
+        result,
+        bond,
+        challenge_ends_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeResult<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Holds the proposer's and any disputer's bond until the proposal settles
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [b"bond_vault", market.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = bond_vault
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = proposer_token_account.owner == proposer.key() @ PredictionMarketError::InvalidVault,
+        constraint = proposer_token_account.mint == mint.key() @ PredictionMarketError::InvalidVault
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(address = market_vault.mint @ PredictionMarketError::InvalidVault)]
+    pub mint: Account<'info, Mint>,
+
+    /// Parent market, only required when proposing for a conditional market
+    pub parent_market: Option<Account<'info, Market>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
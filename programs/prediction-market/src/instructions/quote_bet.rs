@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::*;
+
+/// Read-only, the quote comes back as the instruction's return data so other
+/// programs and clients can simulate a bet
+pub fn quote_market_bet(ctx: Context<QuoteBet>, outcome_index: u8, amount: u64) -> Result<BetQuote> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+
+    require!(
+        matches!(market.pricing, PricingMode::Parimutuel | PricingMode::FixedOdds),
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        (outcome_index as usize) < market.pools.len(),
+        PredictionMarketError::InvalidOutcome
+    );
+
+    let fee_bps = fee_rate_for(
+        curve_fee_bps(market, outcome_index as usize, amount)?,
+        ctx.accounts.user_volume.as_ref().map(|user_volume| user_volume.cumulative_stake),
+    );
+
+    Ok(BetQuote {
+        fee_bps,
+        fee: bet_fee(amount, fee_bps)?,
+        projected_payout: projected_payout(market, outcome_index as usize, amount, clock.unix_timestamp)?,
+        implied_probabilities: implied_probabilities(market)?,
+    })
+}
+
+#[derive(Accounts)]
+pub struct QuoteBet<'info> {
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Bettor the quote is for, only used to derive their volume account
+    pub bettor: UncheckedAccount<'info>,
+
+    /// Bettor's volume account, to quote their discounted fee
+    #[account(
+        seeds = [b"user_volume", bettor.key().as_ref()],
+        bump = user_volume.bump,
+    )]
+    pub user_volume: Option<Account<'info, UserVolume>>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+pub fn add_oracle_signer(ctx: Context<RegisterOracleSigner>, signer: Pubkey) -> Result<()> {
+    require!(
+        ctx.accounts.protocol_config.admin == ctx.accounts.admin.key(),
+        PredictionMarketError::UnauthorizedProtocolAdmin
+    );
+
+    let oracle_signer = &mut ctx.accounts.oracle_signer;
+    oracle_signer.signer = signer;
+    oracle_signer.bump = ctx.bumps.oracle_signer;
+
+    msg!("Oracle signer registered: {}", signer);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(signer: Pubkey)]
+pub struct RegisterOracleSigner<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + OracleSigner::INIT_SPACE,
+        seeds = [b"oracle_signer", signer.as_ref()],
+        bump,
+    )]
+    pub oracle_signer: Account<'info, OracleSigner>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
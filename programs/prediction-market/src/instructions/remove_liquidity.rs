@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn withdraw_liquidity(ctx: Context<RemoveLiquidity>, lp_shares: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    let position = &ctx.accounts.position;
+    require!(
+        matches!(market.pricing, PricingMode::ConstantProduct { .. }),
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    // Liquidity stays in from the betting cutoff until the market is settled
+    if !market.resolved && !market.voided {
+        require!(
+            clock.unix_timestamp < market.start_time,
+            PredictionMarketError::MarketAlreadyStarted
+        );
+        require!(
+            clock.unix_timestamp < market.betting_closes_at(),
+            PredictionMarketError::BettingClosed
+        );
+    }
+    require!(lp_shares > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        position.lp_shares >= lp_shares,
+        PredictionMarketError::InsufficientLpShares
+    );
+
+    // Pro-rata slice of every reserve, which includes the accrued swap fees
+    let portions = market.reserves
+        .iter()
+        .map(|reserve| (*reserve as u128 * lp_shares as u128 / market.lp_supply as u128) as u64)
+        .collect::<Vec<u64>>();
+
+    // While the market is open, full sets of outcome shares are merged back
+    // into tokens. Once settled the shares are claimed like any other.
+    let merged = if market.resolved || market.voided {
+        0
+    } else {
+        portions.iter().copied().min().unwrap_or(0)
+    };
+
+    if merged > 0 {
+        let market_key = market.key();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.market_vault.to_account_info(),
+        };
+        let bump = ctx.bumps.market_vault;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            market_key.as_ref(),
+            &[bump],
+        ]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer(cpi_ctx, merged)?;
+    }
+
+    let market = &mut ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+    for (index, portion) in portions.iter().enumerate() {
+        market.reserves[index] -= portion;
+        market.pools[index] -= merged;
+        position.amounts[index] = position.amounts[index]
+            .checked_add(portion - merged)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    market.collateral = market.collateral
+        .checked_sub(merged)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.lp_supply -= lp_shares;
+    position.lp_shares -= lp_shares;
+
+    msg!(
+        "Liquidity removed: {} LP shares | {} tokens returned | Shares kept: {:?}",
+        lp_shares,
+        merged,
+        portions.iter().map(|portion| portion - merged).collect::<Vec<u64>>()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        constraint = position.user == user.key() @ PredictionMarketError::InvalidVault,
+        constraint = position.market == market.key() @ PredictionMarketError::InvalidVault
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidVault,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -1,12 +1,33 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
-use switchboard_on_demand::on_demand::accounts::pull_feed::PullFeedAccountData;
+use crate::events::*;
+use crate::payout::*;
+use crate::oracle::*;
 
-pub fn resolve_with_switchboard_oracle(ctx: Context<ResolveMarket>) -> Result<()> {
+pub fn resolve_with_oracle(ctx: Context<ResolveMarket>) -> Result<()> {
     let clock = Clock::get()?;
-    let market = &mut ctx.accounts.market;
-    
+    check_resolvable(&ctx.accounts.market, ctx.accounts.parent_market.as_ref(), &clock)?;
+
+    let result = match ctx.accounts.market.oracle_source {
+        OracleSource::Quorum { threshold } => {
+            match read_quorum_result(&mut ctx.accounts.market, ctx.remaining_accounts, threshold, &clock)? {
+                Some(result) => result,
+                None => return Ok(()),
+            }
+        },
+        _ => read_oracle_result(ctx.accounts, &clock)?,
+    };
+
+    settle_result(&mut ctx.accounts.market, result)
+}
+
+/// Whether the market can be settled now
+pub fn check_resolvable(
+    market: &Market,
+    parent_market: Option<&Account<Market>>,
+    clock: &Clock,
+) -> Result<()> {
     // Check timing constraints
     require!(
         clock.unix_timestamp > market.resolution_time, 
@@ -16,78 +37,284 @@ pub fn resolve_with_switchboard_oracle(ctx: Context<ResolveMarket>) -> Result<()
         !market.resolved, 
         PredictionMarketError::MarketAlreadyResolved
     );
+    require!(
+        !market.voided,
+        PredictionMarketError::MarketVoided
+    );
+
+    // Conditional markets only settle once the parent resolved the required way
+    if let Some(parent_key) = market.parent_market {
+        let parent = parent_market.ok_or(PredictionMarketError::InvalidParentMarket)?;
+        require_keys_eq!(
+            parent.key(),
+            parent_key,
+            PredictionMarketError::InvalidParentMarket
+        );
+        require!(
+            parent.resolved_to(market.parent_outcome),
+            PredictionMarketError::ParentConditionNotMet
+        );
+    }
+
+    Ok(())
+}
+
+/// Read the result from whichever source the market settles from
+fn read_oracle_result(accounts: &ResolveMarket, clock: &Clock) -> Result<i64> {
+    let market = &accounts.market;
+    let oracle_feed = accounts.oracle_feed
+        .as_ref()
+        .ok_or(PredictionMarketError::InvalidOracleSource);
+
+    match market.oracle_source {
+        OracleSource::Switchboard => SwitchboardResolver { feed: oracle_feed? }
+            .read_result(market, clock),
+        OracleSource::PriceAccount { .. } => PriceAccountResolver { account: oracle_feed? }
+            .read_result(market, clock),
+        OracleSource::SignedAttestation { .. } => {
+            let instructions = accounts.instructions
+                .as_ref()
+                .ok_or(PredictionMarketError::InvalidOracleSource)?;
+            let oracle_signer = accounts.oracle_signer
+                .as_ref()
+                .ok_or(PredictionMarketError::UnregisteredOracleSigner)?;
+
+            AttestationResolver { instructions, oracle_signer }.read_result(market, clock)
+        },
+        // Quorum markets read several feeds, see `read_quorum_result`
+        OracleSource::Quorum { .. } => err!(PredictionMarketError::InvalidOracleSource),
+        // Optimistic markets settle through their proposal
+        OracleSource::Optimistic { .. } => err!(PredictionMarketError::InvalidOracleSource),
+    }
+}
+
+/// Read every quorum feed passed in and settle on the result enough of them
+/// agree on, see `record_reports`
+fn read_quorum_result(
+    market: &mut Account<Market>,
+    feeds: &[AccountInfo],
+    threshold: u8,
+    clock: &Clock,
+) -> Result<Option<i64>> {
+    let reports = QuorumResolver { feeds }.collect_reports(market, clock)?;
 
-    // Parse the Switchboard On-Demand pull feed
-    let feed = PullFeedAccountData::parse(ctx.accounts.oracle_feed.data.borrow())
-        .map_err(|_| PredictionMarketError::InvalidFeed)?;
-    
-    // Verify this is the correct feed for this market
+    if let Some(result) = record_reports(market, reports.clone(), threshold)? {
+        msg!("Oracle quorum of {} reached on {}", threshold, result);
+        return Ok(Some(result));
+    }
+
+    msg!("Oracle feeds disagree: {:?}", reports);
+
+    emit!(OracleDisagreement {
+        market: market.key(),
+        threshold,
+        reports,
+    });
+
+    Ok(None)
+}
+
+/// Whether the market could settle on `result`, checked before one is proposed
+pub fn check_result(market: &Market, result: i64) -> Result<()> {
     require!(
-        feed.feed_hash == market.oracle_feed,
-        PredictionMarketError::InvalidFeed
+        result >= 0,
+        PredictionMarketError::MatchNotFinished
     );
+    if matches!(market.market_type, MarketType::MatchResult | MarketType::Categorical) {
+        require!(
+            (result as usize) < market.pools.len(),
+            PredictionMarketError::InvalidOracleValue
+        );
+    }
+
+    Ok(())
+}
 
-    // Get the oracle result
-    // Your API returns: -1 = not finished, 0 = away wins, 1 = home wins, 2 = draw
-    
-    // The correct API depends on your switchboard-on-demand version:
-    // Try Option 1 first, if it doesn't compile, use Option 2
-    
-    // Option 1: Pass clock reference (most common in newer versions)
-    let oracle_value = feed.value(clock.slot)
-        .map_err(|_| PredictionMarketError::InvalidOracleValue)?;
-    
-    // Option 2: If Option 1 doesn't compile, try accessing as a field
-    // let oracle_value = feed.value;
-    
-    // Convert to i8 (NOT u8, because we need to handle -1!)
-    let result: i8 = oracle_value
-        .try_into()
-        .map_err(|_| PredictionMarketError::InvalidOracleValue)?;
-
-    msg!("Switchboard On-Demand oracle result: {}", result);
-
-    // Ensure match is finished (not -1)
+/// Settle the market on a raw oracle result
+pub fn settle_result(market: &mut Account<Market>, result: i64) -> Result<()> {
+    // Ensure the event is finished (not -1)
     require!(
-        result >= 0, 
+        result >= 0,
         PredictionMarketError::MatchNotFinished
     );
-    
-    // Validate result is in expected range (0, 1, or 2)
+
+    let refunded = match market.market_type {
+        MarketType::MatchResult | MarketType::Categorical => settle_outcome_index(market, result)?,
+        MarketType::TotalGoals { line } => settle_total_goals(market, result, line)?,
+        MarketType::AsianHandicap { line } => settle_asian_handicap(market, result, line)?,
+        MarketType::CorrectScore { max_goals } => settle_correct_score(market, result, max_goals)?,
+        MarketType::Scalar { lower, upper } => settle_scalar(market, result, lower, upper)?,
+    };
+
+    market.resolved = true;
+    market.disputed = false;
+    market.final_result_value = result;
+
+    if refunded {
+        let total_pool = total_of(&market.pools)?;
+        msg!("Settling as refund of {} tokens", total_pool);
+
+        emit!(MarketRefundSettled {
+            market: market.key(),
+            result,
+            total_pool,
+        });
+    }
+
+    Ok(())
+}
+
+/// The oracle result is the index of the winning outcome
+fn settle_outcome_index(market: &mut Market, result: i64) -> Result<bool> {
+    // Validate result is one of the market's outcomes
     require!(
-        result <= 2, 
+        (result as usize) < market.pools.len(),
         PredictionMarketError::InvalidOracleValue
     );
 
-    // Update market based on result
-    match result {
-        0 => {
-            // Away team wins
-            market.is_draw = false;
-            market.resolved = true;
-            market.outcome = Some(false); // false = away wins
-            msg!("Match resolved: Away wins - {}", market.away_team);
-        },
-        1 => {
-            // Home team wins
-            market.is_draw = false;
-            market.resolved = true;
-            market.outcome = Some(true); // true = home wins
-            msg!("Match resolved: Home wins - {}", market.home_team);
-        },
-        2 => {
-            // Draw
-            market.is_draw = true;
-            market.resolved = true;
-            market.outcome = None;
-            msg!("Match resolved: Draw - {} vs {}", market.home_team, market.away_team);
-        },
-        _ => unreachable!() // Already checked result <= 2
+    let winner = result as u8;
+    if market.market_type == MarketType::MatchResult {
+        match winner {
+            AWAY_OUTCOME => {
+                market.is_draw = false;
+                market.outcome = Some(false); // false = away wins
+                msg!("Match resolved: Away wins - {}", market.away_team);
+            },
+            HOME_OUTCOME => {
+                market.is_draw = false;
+                market.outcome = Some(true); // true = home wins
+                msg!("Match resolved: Home wins - {}", market.home_team);
+            },
+            _ => {
+                market.is_draw = true;
+                market.outcome = None;
+                msg!("Match resolved: Draw - {} vs {}", market.home_team, market.away_team);
+            },
+        }
+    } else {
+        msg!("Market resolved: {}", market.outcome_labels[winner as usize]);
     }
 
-    market.final_result_value = result as i64;
+    // AMM markets redeem every winning share for one token, fixed-odds
+    // markets pay each bet at its locked odds
+    match market.pricing {
+        PricingMode::Parimutuel => {},
+        PricingMode::FixedOdds => return settle_fixed_odds(market, winner as usize),
+        _ => return settle_shares(market, winner as usize),
+    }
 
-    Ok(())
+    // Nobody backed the winning outcome: nobody could claim the pot, so
+    // every bettor gets their stake back instead
+    settle_winner_takes_all(market, winner as usize)
+}
+
+/// The oracle result is the total number of goals, compared against the line
+fn settle_total_goals(market: &mut Market, total_goals: i64, line: i32) -> Result<bool> {
+    let total = total_goals
+        .checked_mul(LINE_SCALE)
+        .ok_or(PredictionMarketError::InvalidOracleValue)?;
+
+    if total == line as i64 {
+        // Whole number line hit exactly: push, every stake is returned
+        msg!("Total goals {} landed on the line, push", total_goals);
+        settle_refund(market);
+        return Ok(true);
+    }
+
+    let winner = if total > line as i64 { OVER_OUTCOME } else { UNDER_OUTCOME };
+    msg!(
+        "Total goals resolved: {} - {}",
+        total_goals,
+        market.outcome_labels[winner as usize]
+    );
+
+    settle_winner_takes_all(market, winner as usize)
+}
+
+/// Split an encoded score feed value into (home goals, away goals)
+fn decode_score(result: i64) -> (i64, i64) {
+    (result / SCORE_ENCODING_BASE, result % SCORE_ENCODING_BASE)
+}
+
+/// The oracle result is the final score, the handicap is added to the home side.
+/// Quarter lines split every stake over the two neighbouring half lines, so
+/// one half can win or lose while the other pushes.
+fn settle_asian_handicap(market: &mut Market, result: i64, line: i32) -> Result<bool> {
+    let (home_goals, away_goals) = decode_score(result);
+    let margin = home_goals
+        .checked_sub(away_goals)
+        .and_then(|margin| margin.checked_mul(LINE_SCALE))
+        .ok_or(PredictionMarketError::InvalidOracleValue)?;
+
+    let quarter = LINE_SCALE as i32 / 4;
+    let lines = if line % (quarter * 2) == 0 {
+        vec![line]
+    } else {
+        vec![line - quarter, line + quarter]
+    };
+
+    let slices = lines
+        .iter()
+        .map(|half_line| {
+            let adjusted = margin + *half_line as i64;
+            if adjusted > 0 {
+                SliceResult::Win(HANDICAP_HOME_OUTCOME as usize)
+            } else if adjusted < 0 {
+                SliceResult::Win(HANDICAP_AWAY_OUTCOME as usize)
+            } else {
+                SliceResult::Push
+            }
+        })
+        .collect::<Vec<SliceResult>>();
+
+    msg!(
+        "Asian handicap resolved: {}-{} | {:?}",
+        home_goals,
+        away_goals,
+        slices
+    );
+
+    settle_split(market, &slices)
+}
+
+/// The oracle result is the final score, scores off the grid fall in "any other"
+fn settle_correct_score(market: &mut Market, result: i64, max_goals: u8) -> Result<bool> {
+    let (home_goals, away_goals) = decode_score(result);
+    let winner = correct_score_outcome(max_goals, home_goals, away_goals);
+
+    msg!(
+        "Correct score resolved: {}-{} - {}",
+        home_goals,
+        away_goals,
+        market.outcome_labels[winner]
+    );
+
+    settle_winner_takes_all(market, winner)
+}
+
+/// The oracle result is clamped to the bounds, longs get the share of the pot
+/// below the value and shorts the share above it
+fn settle_scalar(market: &mut Market, result: i64, lower: i64, upper: i64) -> Result<bool> {
+    let value = result.clamp(lower, upper);
+    let numerator = (value - lower) as u64;
+    let denominator = (upper - lower) as u64;
+
+    msg!(
+        "Scalar market resolved: {} within [{}, {}] | long share {}/{}",
+        result,
+        lower,
+        upper,
+        numerator,
+        denominator
+    );
+
+    settle_long_short(
+        market,
+        LONG_OUTCOME as usize,
+        SHORT_OUTCOME as usize,
+        numerator,
+        denominator,
+    )
 }
 
 #[derive(Accounts)]
@@ -95,7 +322,22 @@ pub struct ResolveMarket<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
 
-    /// CHECK: Switchboard On-Demand pull feed account
-    /// The feed hash stored in the market account is used to verify this is the correct feed
-    pub oracle_feed: AccountInfo<'info>,
+    /// CHECK: Switchboard On-Demand pull feed or price account, verified by
+    /// the resolver for the market's oracle source
+    pub oracle_feed: Option<AccountInfo<'info>>,
+
+    /// CHECK: Instructions sysvar, holds the Ed25519 check of a signed attestation
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<AccountInfo<'info>>,
+
+    /// Registered signer of an attested result
+    #[account(
+        seeds = [b"oracle_signer", oracle_signer.signer.as_ref()],
+        bump = oracle_signer.bump,
+    )]
+    pub oracle_signer: Option<Account<'info, OracleSigner>>,
+
+    /// Parent market, only required when resolving a conditional market
+    pub parent_market: Option<Account<'info, Market>>,
+    // Remaining accounts: the Switchboard feeds of a quorum market
 }
\ No newline at end of file
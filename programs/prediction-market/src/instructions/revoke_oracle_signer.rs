@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Markets relying on the signer can no longer settle from its attestations
+pub fn remove_oracle_signer(ctx: Context<RevokeOracleSigner>) -> Result<()> {
+    require!(
+        ctx.accounts.protocol_config.admin == ctx.accounts.admin.key(),
+        PredictionMarketError::UnauthorizedProtocolAdmin
+    );
+
+    msg!("Oracle signer revoked: {}", ctx.accounts.oracle_signer.signer);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeOracleSigner<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"oracle_signer", oracle_signer.signer.as_ref()],
+        bump = oracle_signer.bump,
+    )]
+    pub oracle_signer: Account<'info, OracleSigner>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::place_bet::release_volume;
+use crate::events::*;
+use crate::lmsr::*;
+
+pub fn sell_shares(ctx: Context<SellShares>, outcome_index: u8, shares: u64, min_proceeds: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(
+        clock.unix_timestamp < market.betting_closes_at(),
+        PredictionMarketError::BettingClosed
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(shares > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        (outcome_index as usize) < market.pools.len(),
+        PredictionMarketError::InvalidOutcome
+    );
+
+    let index = outcome_index as usize;
+    require!(
+        ctx.accounts.position.amounts[index] >= shares,
+        PredictionMarketError::InsufficientShares
+    );
+
+    let proceeds = sell_proceeds(market, index, shares)?;
+    require!(proceeds >= min_proceeds, PredictionMarketError::SlippageExceeded);
+
+    if proceeds > 0 {
+        let market_key = market.key();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.market_vault.to_account_info(),
+        };
+        let bump = ctx.bumps.market_vault;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            market_key.as_ref(),
+            &[bump],
+        ]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer(cpi_ctx, proceeds)?;
+    }
+
+    let market = &mut ctx.accounts.market;
+    market.pools[index] -= shares;
+    market.collateral = market.collateral
+        .checked_sub(proceeds)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let position = &mut ctx.accounts.position;
+    position.amounts[index] -= shares;
+
+    // Selling back takes the proceeds out of the bettor's volume, so buying and
+    // selling the same shares builds no volume
+    release_volume(&mut ctx.accounts.user_volume, position, proceeds)?;
+
+    let prices = scaled_prices(market)?;
+    msg!(
+        "Sold {} shares of {} for {} tokens | Prices: {:?}",
+        shares,
+        market.outcome_labels[index],
+        proceeds,
+        prices
+    );
+
+    emit!(SharesTraded {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        outcome: outcome_index,
+        shares,
+        amount: proceeds,
+        is_buy: false,
+        prices,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SellShares<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        constraint = position.user == user.key() @ PredictionMarketError::InvalidVault,
+        constraint = position.market == market.key() @ PredictionMarketError::InvalidVault
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidVault,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    /// Bettor's volume account, needed if the position counted towards it
+    #[account(
+        mut,
+        seeds = [b"user_volume", user.key().as_ref()],
+        bump = user_volume.bump,
+    )]
+    pub user_volume: Option<Account<'info, UserVolume>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::*;
+
+pub fn settle_leg(ctx: Context<SettleParlayLeg>, leg_index: u8) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let parlay_pool = &mut ctx.accounts.parlay_pool;
+    let parlay = &mut ctx.accounts.parlay;
+
+    require!(
+        parlay.status == ParlayStatus::Open,
+        PredictionMarketError::ParlayNotOpen
+    );
+    require!(
+        (leg_index as usize) < parlay.legs.len(),
+        PredictionMarketError::InvalidParlayLegs
+    );
+
+    let leg = parlay.legs[leg_index as usize];
+    require!(
+        leg.status == LegStatus::Pending,
+        PredictionMarketError::LegAlreadySettled
+    );
+    require!(
+        leg.market == market.key(),
+        PredictionMarketError::LegMarketMismatch
+    );
+    require!(
+        market.resolved || market.voided,
+        PredictionMarketError::MarketNotResolved
+    );
+
+    let (status, factor) = parlay_leg_result(market, leg.outcome as usize, leg.odds)?;
+    parlay.legs[leg_index as usize].status = status;
+    parlay.legs[leg_index as usize].factor = factor;
+    parlay.payout = parlay_payout(parlay.stake, &parlay.legs)?;
+
+    msg!("Parlay leg {} settled: {:?}", leg_index, status);
+
+    if status == LegStatus::Lost {
+        // One losing leg loses the ticket, its reserve is released
+        parlay.status = ParlayStatus::Lost;
+        parlay.payout = 0;
+        parlay_pool.reserved = parlay_pool.reserved
+            .checked_sub(parlay.max_payout)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        msg!("Parlay lost");
+    } else if parlay.legs.iter().all(|leg| leg.status != LegStatus::Pending) {
+        // Keep only the final payout reserved until it is claimed
+        parlay.status = ParlayStatus::Settled;
+        parlay_pool.reserved = parlay_pool.reserved
+            .checked_sub(parlay.max_payout)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_add(parlay.payout)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        msg!("Parlay settled: {} tokens to claim", parlay.payout);
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleParlayLeg<'info> {
+    #[account(
+        mut,
+        constraint = parlay.pool == parlay_pool.key() @ PredictionMarketError::InvalidVault
+    )]
+    pub parlay: Account<'info, Parlay>,
+
+    #[account(mut)]
+    pub parlay_pool: Account<'info, ParlayPool>,
+
+    pub market: Account<'info, Market>,
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::amm::*;
+
+pub fn swap_outcome_shares(
+    ctx: Context<SwapOutcomes>,
+    from_outcome: u8,
+    to_outcome: u8,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(
+        clock.unix_timestamp < market.betting_closes_at(),
+        PredictionMarketError::BettingClosed
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(amount_in > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        from_outcome != to_outcome
+            && (from_outcome as usize) < market.pools.len()
+            && (to_outcome as usize) < market.pools.len(),
+        PredictionMarketError::InvalidOutcome
+    );
+
+    let from = from_outcome as usize;
+    let to = to_outcome as usize;
+    require!(
+        ctx.accounts.position.amounts[from] >= amount_in,
+        PredictionMarketError::InsufficientShares
+    );
+
+    let (amount_out, lp_fee) = swap_output(market, from, to, amount_in)?;
+    require!(amount_out > 0, PredictionMarketError::InsufficientLiquidity);
+    require!(amount_out >= min_amount_out, PredictionMarketError::SlippageExceeded);
+
+    // The whole input, fee included, goes into the pool
+    let market = &mut ctx.accounts.market;
+    market.reserves[from] = market.reserves[from]
+        .checked_add(amount_in)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.reserves[to] -= amount_out;
+
+    let position = &mut ctx.accounts.position;
+    position.amounts[from] -= amount_in;
+    position.amounts[to] = position.amounts[to]
+        .checked_add(amount_out)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    let prices = scaled_reserve_prices(market)?;
+    msg!(
+        "Swapped {} {} shares for {} {} shares | LP fee: {} | Prices: {:?}",
+        amount_in,
+        market.outcome_labels[from],
+        amount_out,
+        market.outcome_labels[to],
+        lp_fee,
+        prices
+    );
+
+    emit!(OutcomeSwapped {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        from_outcome,
+        to_outcome,
+        amount_in,
+        amount_out,
+        lp_fee,
+        prices,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SwapOutcomes<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        constraint = position.user == user.key() @ PredictionMarketError::InvalidVault,
+        constraint = position.market == market.key() @ PredictionMarketError::InvalidVault
+    )]
+    pub position: Account<'info, Position>,
+
+    pub user: Signer<'info>,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+pub fn set_betting_cutoff(ctx: Context<UpdateBettingCutoff>, betting_cutoff: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedBettingWindow
+    );
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(betting_cutoff >= 0, PredictionMarketError::InvalidBettingCutoff);
+
+    market.betting_cutoff = betting_cutoff;
+
+    msg!(
+        "Betting closes {} seconds before the start of {}",
+        betting_cutoff,
+        market.question
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateBettingCutoff<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+pub fn set_fallback_grace_period(
+    ctx: Context<UpdateFallbackGracePeriod>,
+    fallback_grace_period: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedFallback
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(fallback_grace_period >= 0, PredictionMarketError::InvalidGracePeriod);
+    // Bettors took their positions on the grace period they saw, once the
+    // market starts it can only be extended
+    require!(
+        clock.unix_timestamp < market.start_time
+            || fallback_grace_period >= market.fallback_grace_period,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+
+    market.fallback_grace_period = fallback_grace_period;
+
+    msg!("Fallback grace period updated: {} seconds", fallback_grace_period);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFallbackGracePeriod<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
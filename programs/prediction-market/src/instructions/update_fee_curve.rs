@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Pass `None` to go back to the flat market fee
+pub fn set_fee_curve(ctx: Context<UpdateFeeCurve>, fee_curve: Option<FeeCurve>) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedFeeUpdate
+    );
+    require!(
+        matches!(market.pricing, PricingMode::Parimutuel | PricingMode::FixedOdds),
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+
+    if let Some(curve) = fee_curve {
+        require!(
+            curve.min_fee_bps <= market.fee_bps
+                && market.fee_bps <= curve.max_fee_bps
+                && curve.max_fee_bps <= MAX_FEE_BPS,
+            PredictionMarketError::InvalidFeeCurve
+        );
+    }
+
+    market.fee_curve = fee_curve;
+
+    msg!("Fee curve updated: {:?} around {} bps", market.fee_curve, market.fee_bps);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeCurve<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
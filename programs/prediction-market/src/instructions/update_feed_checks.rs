@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+pub fn set_feed_checks(ctx: Context<UpdateFeedChecks>, feed_checks: Option<FeedChecks>) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedUpdater
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(
+        !market.oracle_locked(clock.unix_timestamp),
+        PredictionMarketError::OracleSettingsLocked
+    );
+
+    if let Some(checks) = feed_checks {
+        require!(
+            checks.max_staleness_slots > 0,
+            PredictionMarketError::InvalidFeedChecks
+        );
+    }
+
+    market.feed_checks = feed_checks;
+
+    msg!("Feed checks updated: {:?}", market.feed_checks);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeedChecks<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
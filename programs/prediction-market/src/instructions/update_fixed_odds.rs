@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+pub fn set_fixed_odds(ctx: Context<UpdateFixedOdds>, odds: Vec<u64>) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedBookmaker
+    );
+    require!(
+        market.pricing == PricingMode::FixedOdds,
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(
+        odds.len() == market.pools.len(),
+        PredictionMarketError::InvalidOutcomeCount
+    );
+    require!(
+        odds.iter().all(|odds| *odds > ODDS_SCALE),
+        PredictionMarketError::InvalidOdds
+    );
+
+    // Bets already placed keep the odds they locked in
+    market.fixed_odds = odds;
+
+    msg!("Fixed odds updated: {:?}", market.fixed_odds);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFixedOdds<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+pub fn set_oracle_quorum(
+    ctx: Context<UpdateOracleQuorum>,
+    feeds: Vec<[u8; 32]>,
+    threshold: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedUpdater
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(
+        !market.oracle_locked(clock.unix_timestamp),
+        PredictionMarketError::OracleSettingsLocked
+    );
+
+    require!(
+        feeds.len() >= 2 && feeds.len() <= MAX_QUORUM_FEEDS,
+        PredictionMarketError::InvalidOracleQuorum
+    );
+    // A majority leaves no room for two results to both reach quorum
+    require!(
+        threshold as usize > feeds.len() / 2 && threshold as usize <= feeds.len(),
+        PredictionMarketError::InvalidOracleQuorum
+    );
+    for (i, feed) in feeds.iter().enumerate() {
+        require!(
+            !feeds[..i].contains(feed),
+            PredictionMarketError::InvalidOracleQuorum
+        );
+    }
+
+    market.oracle_source = OracleSource::Quorum { threshold };
+    market.oracle_reports = vec![None; feeds.len()];
+    market.quorum_feeds = feeds;
+    market.disputed = false;
+
+    msg!(
+        "Oracle quorum updated: {} of {} feeds",
+        threshold,
+        market.quorum_feeds.len()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateOracleQuorum<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
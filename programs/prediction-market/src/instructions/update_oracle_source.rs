@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+pub fn set_oracle_source(ctx: Context<UpdateOracleSource>, oracle_source: OracleSource) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedUpdater
+    );
+    require!(!market.resolved, PredictionMarketError::MarketAlreadyResolved);
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(
+        !market.oracle_locked(clock.unix_timestamp),
+        PredictionMarketError::OracleSettingsLocked
+    );
+    // Quorum markets are set up together with their feeds
+    require!(
+        !matches!(oracle_source, OracleSource::Quorum { .. }),
+        PredictionMarketError::InvalidOracleSource
+    );
+    if let OracleSource::Optimistic { bond, challenge_seconds, .. } = oracle_source {
+        require!(
+            bond > 0 && challenge_seconds > 0,
+            PredictionMarketError::InvalidOptimisticConfig
+        );
+    }
+
+    market.oracle_source = oracle_source;
+
+    msg!("Oracle source updated: {:?}", market.oracle_source);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateOracleSource<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
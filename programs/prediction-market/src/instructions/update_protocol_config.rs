@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Only applies to fees charged from now on, shares already accrued on a
+/// market keep the split they were charged at
+pub fn set_protocol_config(
+    ctx: Context<UpdateProtocolConfig>,
+    treasury: Pubkey,
+    protocol_share_bps: u16,
+    referrer_share_bps: u16,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+
+    require!(
+        config.admin == ctx.accounts.admin.key(),
+        PredictionMarketError::UnauthorizedProtocolAdmin
+    );
+    require!(
+        protocol_share_bps as u64 + referrer_share_bps as u64 <= BASIS_POINT_DIVIDER,
+        PredictionMarketError::InvalidFeeSplit
+    );
+
+    config.treasury = treasury;
+    config.protocol_share_bps = protocol_share_bps;
+    config.referrer_share_bps = referrer_share_bps;
+
+    msg!(
+        "Protocol config updated: treasury {} takes {} bps, referrers {} bps",
+        treasury,
+        protocol_share_bps,
+        referrer_share_bps
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
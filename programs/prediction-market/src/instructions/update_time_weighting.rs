@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::*;
+
+/// Shares only mean something if every stake earned them, so the mode can
+/// only change before the first bet. Pass `None` to settle by stake again.
+/// Only markets with a single winner can be weighted, since push and split
+/// settlements return stakes rather than share out a pot.
+pub fn set_time_weighting(
+    ctx: Context<UpdateTimeWeighting>,
+    time_weighting: Option<TimeWeighting>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedTimeWeighting
+    );
+    require!(
+        market.pricing == PricingMode::Parimutuel,
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    require!(
+        matches!(market.market_type, MarketType::MatchResult | MarketType::Categorical),
+        PredictionMarketError::InvalidMarketType
+    );
+    require!(
+        clock.unix_timestamp < market.start_time,
+        PredictionMarketError::MarketAlreadyStarted
+    );
+    require!(!market.voided, PredictionMarketError::MarketVoided);
+    require!(
+        total_of(&market.pools)? == 0,
+        PredictionMarketError::BetsAlreadyPlaced
+    );
+
+    if let Some(weighting) = time_weighting {
+        require!(
+            weighting.max_bonus_bps > 0
+                && weighting.max_bonus_bps <= MAX_EARLY_BONUS_BPS
+                && weighting.ramp_seconds > 0,
+            PredictionMarketError::InvalidTimeWeighting
+        );
+        market.shares = vec![0; market.pools.len()];
+    } else {
+        market.shares = vec![];
+    }
+
+    market.time_weighting = time_weighting;
+
+    msg!("Time weighting updated: {:?}", market.time_weighting);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateTimeWeighting<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
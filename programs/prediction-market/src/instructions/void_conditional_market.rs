@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+pub fn void_on_parent_outcome(ctx: Context<VoidConditionalMarket>) -> Result<()> {
+    let parent = &ctx.accounts.parent_market;
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.parent_market.is_some(),
+        PredictionMarketError::NotConditionalMarket
+    );
+    require!(
+        !market.resolved,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    require!(
+        !market.voided,
+        PredictionMarketError::MarketVoided
+    );
+
+    // Anyone can void the child once the parent has settled the other way;
+    // bettors then get stake and fee back through claim_refund
+    require!(
+        parent.resolved || parent.voided,
+        PredictionMarketError::ParentNotResolved
+    );
+    require!(
+        !parent.resolved_to(market.parent_outcome),
+        PredictionMarketError::ParentConditionMet
+    );
+
+    market.voided = true;
+
+    msg!(
+        "Conditional market voided: {} did not resolve to outcome {}",
+        parent.key(),
+        market.parent_outcome
+    );
+
+    emit!(ConditionalMarketVoided {
+        market: market.key(),
+        parent_market: parent.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VoidConditionalMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        constraint = market.parent_market == Some(parent_market.key()) @ PredictionMarketError::InvalidParentMarket
+    )]
+    pub parent_market: Account<'info, Market>,
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::*;
+
+pub fn withdraw_house(ctx: Context<WithdrawHouseLiquidity>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedBookmaker
+    );
+    require!(
+        market.pricing == PricingMode::FixedOdds,
+        PredictionMarketError::UnsupportedPricingMode
+    );
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    // Liquidity backing bettors can never leave the vault
+    let available = market.collateral.saturating_sub(house_reserved(market)?);
+    require!(
+        amount <= available,
+        PredictionMarketError::InsufficientHouseLiquidity
+    );
+
+    let market_key = market.key();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.market_vault.to_account_info(),
+        to: ctx.accounts.authority_token_account.to_account_info(),
+        authority: ctx.accounts.market_vault.to_account_info(),
+    };
+    let bump = ctx.bumps.market_vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        market_key.as_ref(),
+        &[bump],
+    ]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    anchor_spl::token::transfer(cpi_ctx, amount)?;
+
+    let market = &mut ctx.accounts.market;
+    market.collateral -= amount;
+
+    msg!("House liquidity withdrawn: {} tokens", amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawHouseLiquidity<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ PredictionMarketError::InvalidVault,
+        constraint = authority_token_account.mint == market_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
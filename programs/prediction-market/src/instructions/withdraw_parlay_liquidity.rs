@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+pub fn withdraw_from_parlay_pool(ctx: Context<WithdrawParlayLiquidity>, amount: u64) -> Result<()> {
+    let parlay_pool = &ctx.accounts.parlay_pool;
+
+    require!(
+        parlay_pool.authority == ctx.accounts.authority.key(),
+        PredictionMarketError::UnauthorizedParlayPool
+    );
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    // Liquidity reserved for tickets can never leave the vault
+    let available = ctx.accounts.parlay_vault.amount
+        .checked_sub(parlay_pool.reserved)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    require!(
+        amount <= available,
+        PredictionMarketError::InsufficientParlayLiquidity
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.parlay_vault.to_account_info(),
+        to: ctx.accounts.authority_token_account.to_account_info(),
+        authority: ctx.accounts.parlay_vault.to_account_info(),
+    };
+
+    let pool_key = parlay_pool.key();
+    let vault_seeds = &[b"parlay_vault", pool_key.as_ref(), &[ctx.bumps.parlay_vault]];
+    let signer = &[&vault_seeds[..]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    anchor_spl::token::transfer(cpi_ctx, amount)?;
+
+    msg!("Parlay liquidity withdrawn: {} tokens", amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawParlayLiquidity<'info> {
+    pub parlay_pool: Account<'info, ParlayPool>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ PredictionMarketError::InvalidVault,
+        constraint = authority_token_account.mint == parlay_vault.mint @ PredictionMarketError::InvalidVault
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault", parlay_pool.key().as_ref()],
+        bump,
+    )]
+    pub parlay_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
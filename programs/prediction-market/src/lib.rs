@@ -1,13 +1,20 @@
 #![allow(unexpected_cfgs)] // Allow Solana-specific cfg values emitted by Anchor macros
+#![allow(clippy::too_many_arguments)] // Instruction handlers mirror their Anchor arguments
 
 use anchor_lang::prelude::*;
 mod state;
 mod instructions;
 mod errors;
+mod events;
+mod payout;
+mod lmsr;
+mod amm;
+mod oracle;
 
 use instructions::*;
 use state::*;
 pub use errors::*;
+pub use events::*;
 
 declare_id!("HjX8LkQdV4RMmvzbsxkkszNCX5tVDRdd2hp8xk1RKcJ1");
 
@@ -25,23 +32,388 @@ pub mod prediction_market {
         start_time: i64,
         end_time: i64,
         resolution_time: i64,
-        oracle_feed: [u8; 32]
+        oracle_feed: [u8; 32],
+        market_type: MarketType,
+        fee_bps: u16
     ) -> Result<()> {
-        create_market(ctx, question, home_team, away_team, game_key, start_time, end_time, resolution_time, oracle_feed)
+        create_market(ctx, question, home_team, away_team, game_key, start_time, end_time, resolution_time, oracle_feed, market_type, fee_bps)
     }
 
     pub fn place_bet_on_market(
         ctx: Context<PlaceBet>,
         amount: u64,
-        bet_type: BetType
+        bet_type: BetType,
+        min_implied_payout_bps: Option<u64>
     ) -> Result<()> {
-        place_bet(ctx, amount, bet_type)
+        place_match_result_bet(ctx, amount, bet_type, min_implied_payout_bps)
+    }
+
+    pub fn create_categorical_market(
+        ctx: Context<CreateCategoricalMarket>,
+        question: String,
+        market_key: String,
+        outcome_labels: Vec<String>,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        init_categorical_market(ctx, question, market_key, outcome_labels, start_time, end_time, resolution_time, oracle_feed, fee_bps)
+    }
+
+    pub fn create_binary_market(
+        ctx: Context<CreateBinaryMarket>,
+        question: String,
+        market_key: String,
+        first_outcome: String,
+        second_outcome: String,
+        category: Option<String>,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        init_binary_market(ctx, question, market_key, first_outcome, second_outcome, category, start_time, end_time, resolution_time, oracle_feed, fee_bps)
+    }
+
+    pub fn create_conditional_market(
+        ctx: Context<CreateConditionalMarket>,
+        question: String,
+        market_key: String,
+        outcome_labels: Vec<String>,
+        parent_outcome: u8,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        init_conditional_market(ctx, question, market_key, outcome_labels, parent_outcome, start_time, end_time, resolution_time, oracle_feed, fee_bps)
+    }
+
+    pub fn void_conditional_market(
+        ctx: Context<VoidConditionalMarket>
+    ) -> Result<()> {
+        void_on_parent_outcome(ctx)
+    }
+
+    pub fn create_scalar_market(
+        ctx: Context<CreateScalarMarket>,
+        question: String,
+        market_key: String,
+        lower: i64,
+        upper: i64,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        init_scalar_market(ctx, question, market_key, lower, upper, start_time, end_time, resolution_time, oracle_feed, fee_bps)
+    }
+
+    pub fn create_lmsr_market(
+        ctx: Context<CreateLmsrMarket>,
+        question: String,
+        market_key: String,
+        outcome_labels: Vec<String>,
+        liquidity: u64,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        init_lmsr_market(ctx, question, market_key, outcome_labels, liquidity, start_time, end_time, resolution_time, oracle_feed, fee_bps)
+    }
+
+    pub fn buy_outcome_shares(
+        ctx: Context<PlaceBet>,
+        outcome_index: u8,
+        shares: u64,
+        max_cost: u64
+    ) -> Result<()> {
+        buy_shares(ctx, outcome_index, shares, max_cost)
+    }
+
+    pub fn sell_outcome_shares(
+        ctx: Context<SellShares>,
+        outcome_index: u8,
+        shares: u64,
+        min_proceeds: u64
+    ) -> Result<()> {
+        sell_shares(ctx, outcome_index, shares, min_proceeds)
+    }
+
+    pub fn withdraw_lmsr_liquidity(
+        ctx: Context<WithdrawLmsrLiquidity>
+    ) -> Result<()> {
+        withdraw_lmsr_surplus(ctx)
+    }
+
+    pub fn create_amm_market(
+        ctx: Context<CreateAmmMarket>,
+        question: String,
+        market_key: String,
+        outcome_labels: Vec<String>,
+        lp_fee_bps: u16,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        init_amm_market(ctx, question, market_key, outcome_labels, lp_fee_bps, start_time, end_time, resolution_time, oracle_feed, fee_bps)
+    }
+
+    pub fn add_liquidity(
+        ctx: Context<PlaceBet>,
+        amount: u64
+    ) -> Result<()> {
+        provide_liquidity(ctx, amount)
+    }
+
+    pub fn remove_liquidity(
+        ctx: Context<RemoveLiquidity>,
+        lp_shares: u64
+    ) -> Result<()> {
+        withdraw_liquidity(ctx, lp_shares)
+    }
+
+    pub fn mint_complete_set(
+        ctx: Context<PlaceBet>,
+        amount: u64
+    ) -> Result<()> {
+        mint_outcome_set(ctx, amount)
+    }
+
+    pub fn swap_outcomes(
+        ctx: Context<SwapOutcomes>,
+        from_outcome: u8,
+        to_outcome: u8,
+        amount_in: u64,
+        min_amount_out: u64
+    ) -> Result<()> {
+        swap_outcome_shares(ctx, from_outcome, to_outcome, amount_in, min_amount_out)
+    }
+
+    pub fn create_exchange_market(
+        ctx: Context<CreateExchangeMarket>,
+        question: String,
+        market_key: String,
+        outcome_labels: Vec<String>,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        init_exchange_market(ctx, question, market_key, outcome_labels, start_time, end_time, resolution_time, oracle_feed, fee_bps)
+    }
+
+    pub fn place_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceOrder<'info>>,
+        side: OrderSide,
+        outcome: u8,
+        odds: u64,
+        stake: u64
+    ) -> Result<()> {
+        place_limit_order(ctx, side, outcome, odds, stake)
+    }
+
+    pub fn cancel_order(
+        ctx: Context<CancelOrder>,
+        order_id: u64
+    ) -> Result<()> {
+        cancel_resting_order(ctx, order_id)
+    }
+
+    pub fn create_fixed_odds_market(
+        ctx: Context<CreateFixedOddsMarket>,
+        question: String,
+        market_key: String,
+        outcome_labels: Vec<String>,
+        odds: Vec<u64>,
+        exposure_caps: Vec<u64>,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16
+    ) -> Result<()> {
+        init_fixed_odds_market(ctx, question, market_key, outcome_labels, odds, exposure_caps, start_time, end_time, resolution_time, oracle_feed, fee_bps)
+    }
+
+    pub fn update_fixed_odds(
+        ctx: Context<UpdateFixedOdds>,
+        odds: Vec<u64>
+    ) -> Result<()> {
+        set_fixed_odds(ctx, odds)
+    }
+
+    pub fn fund_house_liquidity(
+        ctx: Context<FundHouseLiquidity>,
+        amount: u64
+    ) -> Result<()> {
+        fund_house(ctx, amount)
+    }
+
+    pub fn withdraw_house_liquidity(
+        ctx: Context<WithdrawHouseLiquidity>,
+        amount: u64
+    ) -> Result<()> {
+        withdraw_house(ctx, amount)
+    }
+
+    pub fn create_user_volume(
+        ctx: Context<CreateUserVolume>
+    ) -> Result<()> {
+        init_user_volume(ctx)
+    }
+
+    pub fn initialize_protocol_config(
+        ctx: Context<InitializeProtocolConfig>,
+        treasury: Pubkey,
+        protocol_share_bps: u16,
+        referrer_share_bps: u16
+    ) -> Result<()> {
+        init_protocol_config(ctx, treasury, protocol_share_bps, referrer_share_bps)
+    }
+
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        treasury: Pubkey,
+        protocol_share_bps: u16,
+        referrer_share_bps: u16
+    ) -> Result<()> {
+        set_protocol_config(ctx, treasury, protocol_share_bps, referrer_share_bps)
+    }
+
+    pub fn create_referral(
+        ctx: Context<CreateReferral>
+    ) -> Result<()> {
+        init_referral(ctx)
+    }
+
+    pub fn update_betting_cutoff(
+        ctx: Context<UpdateBettingCutoff>,
+        betting_cutoff: i64
+    ) -> Result<()> {
+        set_betting_cutoff(ctx, betting_cutoff)
+    }
+
+    pub fn update_fee_curve(
+        ctx: Context<UpdateFeeCurve>,
+        fee_curve: Option<FeeCurve>
+    ) -> Result<()> {
+        set_fee_curve(ctx, fee_curve)
+    }
+
+    pub fn update_time_weighting(
+        ctx: Context<UpdateTimeWeighting>,
+        time_weighting: Option<TimeWeighting>
+    ) -> Result<()> {
+        set_time_weighting(ctx, time_weighting)
+    }
+
+    pub fn place_bet_on_outcome(
+        ctx: Context<PlaceBet>,
+        amount: u64,
+        outcome_index: u8,
+        min_implied_payout_bps: Option<u64>
+    ) -> Result<()> {
+        place_bet(ctx, amount, outcome_index, min_implied_payout_bps)
+    }
+
+    pub fn quote_bet(
+        ctx: Context<QuoteBet>,
+        outcome_index: u8,
+        amount: u64
+    ) -> Result<BetQuote> {
+        quote_market_bet(ctx, outcome_index, amount)
+    }
+
+    pub fn update_oracle_source(
+        ctx: Context<UpdateOracleSource>,
+        oracle_source: OracleSource
+    ) -> Result<()> {
+        set_oracle_source(ctx, oracle_source)
+    }
+
+    pub fn update_oracle_quorum(
+        ctx: Context<UpdateOracleQuorum>,
+        feeds: Vec<[u8; 32]>,
+        threshold: u8
+    ) -> Result<()> {
+        set_oracle_quorum(ctx, feeds, threshold)
+    }
+
+    pub fn update_feed_checks(
+        ctx: Context<UpdateFeedChecks>,
+        feed_checks: Option<FeedChecks>
+    ) -> Result<()> {
+        set_feed_checks(ctx, feed_checks)
+    }
+
+    pub fn register_oracle_signer(
+        ctx: Context<RegisterOracleSigner>,
+        signer: Pubkey
+    ) -> Result<()> {
+        add_oracle_signer(ctx, signer)
+    }
+
+    pub fn revoke_oracle_signer(
+        ctx: Context<RevokeOracleSigner>
+    ) -> Result<()> {
+        remove_oracle_signer(ctx)
     }
 
     pub fn resolve_market(
         ctx: Context<ResolveMarket>
     ) -> Result<()> {
-        resolve_with_switchboard_oracle(ctx)
+        resolve_with_oracle(ctx)
+    }
+
+    pub fn propose_result(
+        ctx: Context<ProposeResult>,
+        result: i64
+    ) -> Result<()> {
+        open_proposal(ctx, result)
+    }
+
+    pub fn dispute_proposal(
+        ctx: Context<DisputeProposal>
+    ) -> Result<()> {
+        challenge_proposal(ctx)
+    }
+
+    pub fn finalize_proposal(
+        ctx: Context<FinalizeProposal>,
+        ruling: Option<i64>
+    ) -> Result<()> {
+        settle_proposal(ctx, ruling)
+    }
+
+    pub fn update_fallback_grace_period(
+        ctx: Context<UpdateFallbackGracePeriod>,
+        fallback_grace_period: i64
+    ) -> Result<()> {
+        set_fallback_grace_period(ctx, fallback_grace_period)
+    }
+
+    pub fn announce_fallback_resolution(
+        ctx: Context<AnnounceFallbackResolution>,
+        result: i64
+    ) -> Result<()> {
+        announce_fallback(ctx, result)
+    }
+
+    pub fn execute_fallback_resolution(
+        ctx: Context<ExecuteFallbackResolution>
+    ) -> Result<()> {
+        execute_fallback(ctx)
     }
 
     pub fn claim_winnings_from_market(
@@ -54,6 +426,68 @@ pub mod prediction_market {
     ) -> Result<()> {
         collect_fees(ctx)
     }
+
+    pub fn claim_protocol_fees(
+        ctx: Context<ClaimProtocolFees>
+    ) -> Result<()> {
+        collect_protocol_fees(ctx)
+    }
+
+    pub fn claim_referral_fees(
+        ctx: Context<ClaimReferralFees>
+    ) -> Result<()> {
+        collect_referral_fees(ctx)
+    }
+
+    pub fn cancel_market(
+        ctx: Context<CancelMarket>
+    ) -> Result<()> {
+        void_market(ctx)
+    }
+
+    pub fn claim_refund_from_market(
+        ctx: Context<ClaimRefund>
+    ) -> Result<()> {
+        claim_refund(ctx)
+    }
+
+    pub fn create_parlay_pool(
+        ctx: Context<CreateParlayPool>,
+        max_ticket_payout: u64,
+        min_leg_pool: u64,
+        margin_bps: u16
+    ) -> Result<()> {
+        init_parlay_pool(ctx, max_ticket_payout, min_leg_pool, margin_bps)
+    }
+
+    pub fn withdraw_parlay_liquidity(
+        ctx: Context<WithdrawParlayLiquidity>,
+        amount: u64
+    ) -> Result<()> {
+        withdraw_from_parlay_pool(ctx, amount)
+    }
+
+    pub fn open_parlay<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenParlay<'info>>,
+        parlay_id: u64,
+        stake: u64,
+        outcomes: Vec<u8>
+    ) -> Result<()> {
+        open_parlay_ticket(ctx, parlay_id, stake, outcomes)
+    }
+
+    pub fn settle_parlay_leg(
+        ctx: Context<SettleParlayLeg>,
+        leg_index: u8
+    ) -> Result<()> {
+        settle_leg(ctx, leg_index)
+    }
+
+    pub fn claim_parlay(
+        ctx: Context<ClaimParlay>
+    ) -> Result<()> {
+        claim_parlay_payout(ctx)
+    }
     
 }
 
@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+// Cost function of a logarithmic market scoring rule:
+//   C(q) = b * ln(sum(exp(q_i / b)))
+// with q_i the outstanding shares of outcome i and b the liquidity parameter.
+// Floating point is emulated in software on-chain, so results are
+// deterministic; every amount is rounded in the maker's favour. Capping the
+// liquidity and the shares per outcome keeps the float error far below the
+// one token unit that rounding absorbs, so a round trip never pays out more
+// than it cost.
+
+/// Liquidity parameter of an LMSR market
+pub fn liquidity_of(market: &Market) -> Result<u64> {
+    match market.pricing {
+        PricingMode::Lmsr { liquidity } => Ok(liquidity),
+        _ => err!(PredictionMarketError::UnsupportedPricingMode),
+    }
+}
+
+/// Worst case loss of the maker, which the creator funds up front: b * ln(n)
+pub fn max_loss(liquidity: u64, outcome_count: usize) -> Result<u64> {
+    to_amount((liquidity as f64 * (outcome_count as f64).ln()).ceil())
+}
+
+fn cost(shares: &[u64], liquidity: u64) -> f64 {
+    let b = liquidity as f64;
+    // Shift by the largest quantity so exp() cannot overflow
+    let max = shares.iter().copied().max().unwrap_or(0) as f64;
+    let sum: f64 = shares
+        .iter()
+        .map(|quantity| ((*quantity as f64 - max) / b).exp())
+        .sum();
+
+    max + b * sum.ln()
+}
+
+/// Convert a rounded float back into a token amount
+pub fn to_amount(value: f64) -> Result<u64> {
+    require!(
+        value.is_finite() && value >= 0.0 && value <= u64::MAX as f64,
+        PredictionMarketError::MathOverflow
+    );
+    Ok(value as u64)
+}
+
+/// Tokens a buyer pays for `shares` of `outcome`, rounded up
+pub fn buy_cost(market: &Market, outcome: usize, shares: u64) -> Result<u64> {
+    let liquidity = liquidity_of(market)?;
+    let mut after = market.pools.clone();
+    after[outcome] = after[outcome]
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    require!(
+        after[outcome] <= liquidity.saturating_mul(MAX_LMSR_SHARES_PER_LIQUIDITY),
+        PredictionMarketError::ShareCapExceeded
+    );
+
+    to_amount((cost(&after, liquidity) - cost(&market.pools, liquidity)).ceil())
+}
+
+/// Tokens a seller receives for `shares` of `outcome`, rounded down
+pub fn sell_proceeds(market: &Market, outcome: usize, shares: u64) -> Result<u64> {
+    let liquidity = liquidity_of(market)?;
+    let mut after = market.pools.clone();
+    after[outcome] = after[outcome]
+        .checked_sub(shares)
+        .ok_or(PredictionMarketError::InsufficientShares)?;
+
+    to_amount((cost(&market.pools, liquidity) - cost(&after, liquidity)).floor().max(0.0))
+}
+
+fn prices(market: &Market) -> Result<Vec<f64>> {
+    let b = liquidity_of(market)? as f64;
+    let max = market.pools.iter().copied().max().unwrap_or(0) as f64;
+    let weights = market.pools
+        .iter()
+        .map(|quantity| ((*quantity as f64 - max) / b).exp())
+        .collect::<Vec<f64>>();
+    let sum: f64 = weights.iter().sum();
+
+    Ok(weights.iter().map(|weight| weight / sum).collect())
+}
+
+/// Current price of every outcome scaled by PRICE_SCALE
+pub fn scaled_prices(market: &Market) -> Result<Vec<u64>> {
+    prices(market)?
+        .iter()
+        .map(|price| to_amount((price * PRICE_SCALE as f64).round()))
+        .collect()
+}
+
+/// Settle a voided LMSR market by buying every share back at its last price.
+/// The total never exceeds the largest outstanding quantity, which the
+/// collateral always covers.
+pub fn settle_at_last_prices(market: &mut Market) -> Result<()> {
+    let payouts = prices(market)?
+        .iter()
+        .zip(market.pools.iter())
+        .map(|(price, quantity)| to_amount((price * *quantity as f64).floor()))
+        .collect::<Result<Vec<u64>>>()?;
+
+    market.payouts = payouts;
+    market.refund_mode = true;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAP: u64 = MAX_LMSR_LIQUIDITY * MAX_LMSR_SHARES_PER_LIQUIDITY;
+
+    fn lmsr_market(outcome_count: usize) -> Market {
+        Market {
+            pricing: PricingMode::Lmsr { liquidity: MAX_LMSR_LIQUIDITY },
+            ..Market::new(
+                Pubkey::default(),
+                "Will it rain on match day?".to_string(),
+                "LMSR_MARKET".to_string(),
+                MarketType::Categorical,
+                vec!["Outcome".to_string(); outcome_count],
+                0,
+                1,
+                2,
+                [0; 32],
+                0,
+                Pubkey::default(),
+                0,
+            )
+        }
+    }
+
+    /// Buy `shares` of `outcome` and sell them straight back
+    fn round_trip(market: &mut Market, outcome: usize, shares: u64) -> (u64, u64) {
+        let cost = buy_cost(market, outcome, shares).unwrap();
+        market.pools[outcome] += shares;
+        let proceeds = sell_proceeds(market, outcome, shares).unwrap();
+        market.pools[outcome] -= shares;
+        (cost, proceeds)
+    }
+
+    #[test]
+    fn round_trip_up_to_the_cap_does_not_leak() {
+        let mut market = lmsr_market(2);
+
+        let (cost, proceeds) = round_trip(&mut market, 0, CAP);
+        assert!(proceeds <= cost);
+    }
+
+    #[test]
+    fn small_round_trips_at_the_cap_do_not_leak() {
+        for outcome_count in [2, MAX_OUTCOMES] {
+            for shares in [1, 7, 999, 1_000_000, 123_456_789_012] {
+                let mut market = lmsr_market(outcome_count);
+                market.pools[0] = CAP - shares;
+                market.pools[1] = CAP / 3;
+
+                for outcome in [0, 1] {
+                    let (cost, proceeds) = round_trip(&mut market, outcome, shares);
+                    assert!(proceeds <= cost, "{} shares of {} leaked", shares, outcome);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn buying_past_the_cap_fails() {
+        let mut market = lmsr_market(2);
+        market.pools[0] = CAP;
+
+        assert_eq!(
+            buy_cost(&market, 0, 1).unwrap_err(),
+            error!(PredictionMarketError::ShareCapExceeded)
+        );
+    }
+}
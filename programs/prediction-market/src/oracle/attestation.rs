@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::state::*;
+use crate::errors::*;
+use super::OracleResolver;
+
+// Ed25519 program instruction layout for a single signature with its public
+// key and message carried in the instruction itself
+const SIGNATURE_COUNT_OFFSET: usize = 0;
+const OFFSETS_START: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const SAME_INSTRUCTION: u16 = u16::MAX;
+const PUBKEY_LEN: usize = 32;
+
+/// Signed message: the market's address followed by the result as a
+/// little-endian i64
+pub const ATTESTATION_MESSAGE_LEN: usize = 40;
+
+/// Result attestation from a registered sports-data signer. The signature is
+/// checked by an Ed25519 program instruction placed right before the resolve
+/// instruction, this only confirms who signed what.
+pub struct AttestationResolver<'a, 'info> {
+    pub instructions: &'a AccountInfo<'info>,
+    pub oracle_signer: &'a OracleSigner,
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(PredictionMarketError::InvalidAttestation)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn slice_at(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = offset as usize;
+    data.get(start..start + len)
+        .ok_or_else(|| error!(PredictionMarketError::InvalidAttestation))
+}
+
+impl OracleResolver for AttestationResolver<'_, '_> {
+    fn read_result(&self, market: &Account<Market>, _clock: &Clock) -> Result<i64> {
+        let OracleSource::SignedAttestation { signer } = market.oracle_source else {
+            return err!(PredictionMarketError::InvalidOracleSource);
+        };
+        require_keys_eq!(
+            self.oracle_signer.signer,
+            signer,
+            PredictionMarketError::UnregisteredOracleSigner
+        );
+
+        let current = load_current_index_checked(self.instructions)?;
+        require!(current > 0, PredictionMarketError::InvalidAttestation);
+        let verify = load_instruction_at_checked(current as usize - 1, self.instructions)?;
+        require_keys_eq!(
+            verify.program_id,
+            ed25519_program::ID,
+            PredictionMarketError::InvalidAttestation
+        );
+
+        let data = &verify.data;
+        require!(
+            data.get(SIGNATURE_COUNT_OFFSET) == Some(&1),
+            PredictionMarketError::InvalidAttestation
+        );
+
+        // signature offset, signature ix, pubkey offset, pubkey ix,
+        // message offset, message size, message ix
+        let offsets = (0..OFFSETS_LEN / 2)
+            .map(|field| read_u16(data, OFFSETS_START + field * 2))
+            .collect::<Result<Vec<u16>>>()?;
+        require!(
+            offsets[1] == SAME_INSTRUCTION
+                && offsets[3] == SAME_INSTRUCTION
+                && offsets[6] == SAME_INSTRUCTION,
+            PredictionMarketError::InvalidAttestation
+        );
+
+        let pubkey = slice_at(data, offsets[2], PUBKEY_LEN)?;
+        require!(
+            pubkey == signer.as_ref(),
+            PredictionMarketError::UnregisteredOracleSigner
+        );
+
+        require!(
+            offsets[5] as usize == ATTESTATION_MESSAGE_LEN,
+            PredictionMarketError::InvalidAttestation
+        );
+        let message = slice_at(data, offsets[4], ATTESTATION_MESSAGE_LEN)?;
+        require!(
+            &message[..PUBKEY_LEN] == market.key().as_ref(),
+            PredictionMarketError::InvalidAttestation
+        );
+
+        let result = i64::from_le_bytes(message[PUBKEY_LEN..].try_into().unwrap());
+
+        msg!("Attested oracle result: {} signed by {}", result, signer);
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+pub mod attestation;
+pub mod price_account;
+pub mod quorum;
+pub mod switchboard;
+
+pub use attestation::*;
+pub use price_account::*;
+pub use quorum::*;
+pub use switchboard::*;
+
+// Every oracle source reports the same raw result, whose meaning depends on
+// the market type: -1 always means the event is not finished yet
+// Match result feeds return: 0 = away wins, 1 = home wins, 2 = draw
+// Categorical feeds return the winning outcome index
+// Total goals feeds return the total number of goals scored
+// Score feeds return home_goals * SCORE_ENCODING_BASE + away_goals
+// Scalar feeds return the measured value itself
+
+/// A source a market can be settled from, verified against what the market
+/// expects before its result is read
+pub trait OracleResolver {
+    /// Raw result the source reports for `market`
+    fn read_result(&self, market: &Account<Market>, clock: &Clock) -> Result<i64>;
+}
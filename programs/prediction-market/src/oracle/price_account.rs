@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::OracleResolver;
+
+/// Pyth oracle program, the only owner whose price accounts are trusted
+pub mod price_program {
+    anchor_lang::declare_id!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+}
+
+// Pyth-style price account layout, only the fields read here
+const MAGIC: u32 = 0xa1b2c3d4;
+const PRICE_ACCOUNT_TYPE: u32 = 3;
+const TRADING_STATUS: u32 = 1;
+const MAGIC_OFFSET: usize = 0;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPONENT_OFFSET: usize = 20;
+const AGGREGATE_PRICE_OFFSET: usize = 208;
+const AGGREGATE_STATUS_OFFSET: usize = 224;
+const MIN_ACCOUNT_LEN: usize = 240;
+
+/// Pyth-style price account publishing the result as its aggregate price
+pub struct PriceAccountResolver<'a, 'info> {
+    pub account: &'a AccountInfo<'info>,
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+impl OracleResolver for PriceAccountResolver<'_, '_> {
+    fn read_result(&self, market: &Account<Market>, _clock: &Clock) -> Result<i64> {
+        let OracleSource::PriceAccount { account } = market.oracle_source else {
+            return err!(PredictionMarketError::InvalidOracleSource);
+        };
+        require_keys_eq!(self.account.key(), account, PredictionMarketError::InvalidFeed);
+        require_keys_eq!(
+            *self.account.owner,
+            price_program::ID,
+            PredictionMarketError::InvalidPriceAccountOwner
+        );
+
+        let data = self.account.data.borrow();
+        require!(
+            data.len() >= MIN_ACCOUNT_LEN
+                && read_u32(&data, MAGIC_OFFSET) == MAGIC
+                && read_u32(&data, ACCOUNT_TYPE_OFFSET) == PRICE_ACCOUNT_TYPE,
+            PredictionMarketError::InvalidFeed
+        );
+
+        // Only a price the publishers currently agree on settles a market
+        require!(
+            read_u32(&data, AGGREGATE_STATUS_OFFSET) == TRADING_STATUS,
+            PredictionMarketError::OraclePriceUnavailable
+        );
+
+        let exponent = read_u32(&data, EXPONENT_OFFSET) as i32;
+        let price = i64::from_le_bytes(
+            data[AGGREGATE_PRICE_OFFSET..AGGREGATE_PRICE_OFFSET + 8].try_into().unwrap()
+        );
+
+        // Results are whole numbers, a negative exponent is truncated away
+        let scale = 10i64
+            .checked_pow(exponent.unsigned_abs())
+            .ok_or(PredictionMarketError::InvalidOracleValue)?;
+        let result = if exponent < 0 {
+            price / scale
+        } else {
+            price
+                .checked_mul(scale)
+                .ok_or(PredictionMarketError::InvalidOracleValue)?
+        };
+
+        msg!("Price account oracle result: {} (price {} expo {})", result, price, exponent);
+
+        Ok(result)
+    }
+}
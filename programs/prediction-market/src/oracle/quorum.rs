@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::on_demand::accounts::pull_feed::PullFeedAccountData;
+use crate::state::*;
+use crate::errors::*;
+use super::{switchboard_program_id, SwitchboardResolver};
+
+/// Switchboard feeds of a quorum market, passed in any order
+pub struct QuorumResolver<'a, 'info> {
+    pub feeds: &'a [AccountInfo<'info>],
+}
+
+impl QuorumResolver<'_, '_> {
+    /// Latest result of each of the market's feeds, indexed like `quorum_feeds`.
+    /// A feed that was not passed, has no valid value, fails the market's feed
+    /// checks or still reports the event as unfinished has nothing to report.
+    pub fn collect_reports(&self, market: &Market, clock: &Clock) -> Result<Vec<Option<i64>>> {
+        let mut reports = vec![None; market.quorum_feeds.len()];
+
+        for feed in self.feeds {
+            require_keys_eq!(
+                *feed.owner,
+                switchboard_program_id(),
+                PredictionMarketError::InvalidFeed
+            );
+            let feed_hash = PullFeedAccountData::parse(feed.data.borrow())
+                .map_err(|_| PredictionMarketError::InvalidFeed)?
+                .feed_hash;
+            let index = market.quorum_feeds
+                .iter()
+                .position(|quorum_feed| *quorum_feed == feed_hash)
+                .ok_or(PredictionMarketError::InvalidFeed)?;
+
+            reports[index] = SwitchboardResolver { feed }
+                .read_feed(&feed_hash, market.feed_checks.as_ref(), clock)
+                .ok()
+                .filter(|result| *result >= 0);
+        }
+
+        Ok(reports)
+    }
+}
+
+/// Result reported by at least `threshold` feeds
+pub fn quorum_result(reports: &[Option<i64>], threshold: u8) -> Option<i64> {
+    reports
+        .iter()
+        .flatten()
+        .copied()
+        .find(|result| {
+            reports.iter().filter(|report| **report == Some(*result)).count() >= threshold as usize
+        })
+}
+
+/// Whether at least two feeds reported different results
+pub fn reports_disagree(reports: &[Option<i64>]) -> bool {
+    let mut results = reports.iter().flatten();
+    match results.next() {
+        Some(first) => results.any(|result| result != first),
+        None => false,
+    }
+}
+
+/// Record what each quorum feed reports and return the result enough of them
+/// agree on. If they disagree the market is left disputed until a later
+/// attempt reaches quorum.
+pub fn record_reports(market: &mut Market, reports: Vec<Option<i64>>, threshold: u8) -> Result<Option<i64>> {
+    if let Some(result) = quorum_result(&reports, threshold) {
+        market.oracle_reports = reports;
+        return Ok(Some(result));
+    }
+
+    // Too few feeds have reported yet, nothing to record
+    require!(
+        reports_disagree(&reports),
+        PredictionMarketError::QuorumNotReached
+    );
+
+    market.oracle_reports = reports;
+    market.disputed = true;
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quorum_market() -> Market {
+        let mut market = Market::new(
+            Pubkey::default(),
+            "Will home team win?".to_string(),
+            "GAME_QUORUM".to_string(),
+            MarketType::MatchResult,
+            vec!["Away".to_string(), "Home".to_string(), "Draw".to_string()],
+            0,
+            1,
+            2,
+            [0; 32],
+            0,
+            Pubkey::default(),
+            0,
+        );
+        market.oracle_source = OracleSource::Quorum { threshold: 2 };
+        market.quorum_feeds = vec![[1; 32], [2; 32], [3; 32]];
+        market.oracle_reports = vec![None; 3];
+        market
+    }
+
+    #[test]
+    fn agreeing_feeds_reach_quorum() {
+        let mut market = quorum_market();
+        let reports = vec![Some(1), None, Some(1)];
+
+        assert_eq!(record_reports(&mut market, reports.clone(), 2).unwrap(), Some(1));
+        assert_eq!(market.oracle_reports, reports);
+        assert!(!market.disputed);
+    }
+
+    #[test]
+    fn split_feeds_dispute_the_market() {
+        let mut market = quorum_market();
+        let reports = vec![Some(1), Some(0), None];
+
+        assert_eq!(record_reports(&mut market, reports.clone(), 2).unwrap(), None);
+        assert_eq!(market.oracle_reports, reports);
+        assert!(market.disputed);
+    }
+
+    #[test]
+    fn too_few_reports_fail_to_reach_quorum() {
+        let mut market = quorum_market();
+
+        assert_eq!(
+            record_reports(&mut market, vec![Some(1), None, None], 2).unwrap_err(),
+            error!(PredictionMarketError::QuorumNotReached)
+        );
+        assert_eq!(market.oracle_reports, vec![None; 3]);
+        assert!(!market.disputed);
+    }
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::on_demand::accounts::pull_feed::PullFeedAccountData;
+use switchboard_on_demand::Owner as _;
+use switchboard_on_demand::prelude::rust_decimal::Decimal;
+use crate::state::*;
+use crate::errors::*;
+use super::OracleResolver;
+
+/// Program that owns Switchboard On-Demand pull feeds on this cluster
+pub fn switchboard_program_id() -> Pubkey {
+    Pubkey::new_from_array(PullFeedAccountData::owner().to_bytes())
+}
+
+/// Switchboard On-Demand pull feed, matched to the market by its feed hash
+pub struct SwitchboardResolver<'a, 'info> {
+    pub feed: &'a AccountInfo<'info>,
+}
+
+impl SwitchboardResolver<'_, '_> {
+    /// Current value of the feed, which must hash to `feed_hash` and pass
+    /// the market's feed checks
+    pub fn read_feed(
+        &self,
+        feed_hash: &[u8; 32],
+        checks: Option<&FeedChecks>,
+        clock: &Clock,
+    ) -> Result<i64> {
+        require_keys_eq!(
+            *self.feed.owner,
+            switchboard_program_id(),
+            PredictionMarketError::InvalidFeed
+        );
+        let feed = PullFeedAccountData::parse(self.feed.data.borrow())
+            .map_err(|_| PredictionMarketError::InvalidFeed)?;
+
+        // Verify this is the correct feed for this market
+        require!(
+            feed.feed_hash == *feed_hash,
+            PredictionMarketError::InvalidFeed
+        );
+
+        if let Some(checks) = checks {
+            let result_slot = feed.result.result_slot()
+                .ok_or(PredictionMarketError::StaleOracleResult)?;
+            require!(
+                clock.slot.saturating_sub(result_slot) <= checks.max_staleness_slots,
+                PredictionMarketError::StaleOracleResult
+            );
+            require!(
+                feed.result.num_samples >= checks.min_samples,
+                PredictionMarketError::InsufficientOracleSamples
+            );
+
+            let std_dev = feed.std_dev()
+                .ok_or(PredictionMarketError::StaleOracleResult)?;
+            require!(
+                std_dev <= Decimal::from(checks.max_std_dev),
+                PredictionMarketError::OracleDeviationTooHigh
+            );
+        }
+
+        let oracle_value = feed.value(clock.slot)
+            .map_err(|_| PredictionMarketError::InvalidOracleValue)?;
+
+        let result: i64 = oracle_value
+            .try_into()
+            .map_err(|_| PredictionMarketError::InvalidOracleValue)?;
+
+        msg!("Switchboard On-Demand oracle result: {}", result);
+
+        Ok(result)
+    }
+}
+
+impl OracleResolver for SwitchboardResolver<'_, '_> {
+    fn read_result(&self, market: &Account<Market>, clock: &Clock) -> Result<i64> {
+        self.read_feed(&market.oracle_feed, market.feed_checks.as_ref(), clock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use switchboard_on_demand::on_demand::accounts::pull_feed::PRECISION;
+    use switchboard_on_demand::Discriminator as _;
+
+    const FEED_HASH: [u8; 32] = [7; 32];
+    const SLOT: u64 = 1_000;
+    const CHECKS: FeedChecks = FeedChecks {
+        max_staleness_slots: 10,
+        min_samples: 3,
+        max_std_dev: 0,
+    };
+
+    /// Read a feed reporting a home win with the given result statistics, as
+    /// Switchboard lays it out in the account
+    fn read(result_slot: u64, num_samples: u8, std_dev: i128) -> Result<i64> {
+        read_owned_by(switchboard_program_id(), result_slot, num_samples, std_dev)
+    }
+
+    fn read_owned_by(owner: Pubkey, result_slot: u64, num_samples: u8, std_dev: i128) -> Result<i64> {
+        let mut feed: PullFeedAccountData = bytemuck::Zeroable::zeroed();
+        feed.feed_hash = FEED_HASH;
+        feed.max_staleness = SLOT as u32;
+        feed.result.value = 10i128.pow(PRECISION);
+        feed.result.slot = result_slot;
+        feed.result.num_samples = num_samples;
+        feed.result.std_dev = std_dev;
+
+        // Backed by u128s so the feed after the discriminator is aligned
+        let size = 8 + std::mem::size_of::<PullFeedAccountData>();
+        let mut backing = vec![0u128; size / 16 + 2];
+        let data = &mut bytemuck::cast_slice_mut::<u128, u8>(&mut backing)[8..8 + size];
+        data[..8].copy_from_slice(PullFeedAccountData::DISCRIMINATOR);
+        data[8..].copy_from_slice(bytemuck::bytes_of(&feed));
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let feed_info = AccountInfo::new(&key, false, false, &mut lamports, data, &owner, false, 0);
+        let clock = Clock { slot: SLOT, ..Clock::default() };
+
+        SwitchboardResolver { feed: &feed_info }.read_feed(&FEED_HASH, Some(&CHECKS), &clock)
+    }
+
+    #[test]
+    fn reads_a_result_that_passes_the_checks() {
+        assert_eq!(read(SLOT - 10, 3, 0).unwrap(), 1);
+    }
+
+    #[test]
+    fn fails_on_a_stale_result() {
+        assert_eq!(
+            read(SLOT - 11, 3, 0).unwrap_err(),
+            error!(PredictionMarketError::StaleOracleResult)
+        );
+    }
+
+    #[test]
+    fn fails_on_too_few_samples() {
+        assert_eq!(
+            read(SLOT, 2, 0).unwrap_err(),
+            error!(PredictionMarketError::InsufficientOracleSamples)
+        );
+    }
+
+    #[test]
+    fn fails_on_samples_spread_too_wide() {
+        assert_eq!(
+            read(SLOT, 3, 10i128.pow(PRECISION) / 2).unwrap_err(),
+            error!(PredictionMarketError::OracleDeviationTooHigh)
+        );
+    }
+
+    #[test]
+    fn fails_on_a_feed_not_owned_by_switchboard() {
+        assert_eq!(
+            read_owned_by(Pubkey::new_unique(), SLOT, 3, 0).unwrap_err(),
+            error!(PredictionMarketError::InvalidFeed)
+        );
+    }
+}
@@ -0,0 +1,509 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Sum of a list of per-outcome amounts
+pub fn total_of(amounts: &[u64]) -> Result<u64> {
+    amounts.iter().try_fold(0u64, |total, amount| {
+        total
+            .checked_add(*amount)
+            .ok_or_else(|| error!(PredictionMarketError::MathOverflow))
+    })
+}
+
+/// Fee rate a bettor pays on a market once their volume tier is applied
+pub fn fee_rate_for(market_fee_bps: u16, cumulative_stake: Option<u64>) -> u16 {
+    let share = VOLUME_FEE_TIERS
+        .iter()
+        .rev()
+        .find(|(threshold, _)| cumulative_stake.unwrap_or(0) >= *threshold)
+        .map(|(_, share)| *share)
+        .unwrap_or(BASIS_POINT_DIVIDER);
+
+    (market_fee_bps as u64 * share / BASIS_POINT_DIVIDER) as u16
+}
+
+/// Fee rate of a bet of `amount` on `outcome` before volume discounts, from
+/// the market's fee curve if it has one
+pub fn curve_fee_bps(market: &Market, outcome: usize, amount: u64) -> Result<u16> {
+    let Some(curve) = market.fee_curve else {
+        return Ok(market.fee_bps);
+    };
+
+    let pot = total_of(&market.pools)?
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let pool = market.pools[outcome]
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let share = pro_rata_share(pool, BASIS_POINT_DIVIDER, pot)?;
+    let even = BASIS_POINT_DIVIDER / market.pools.len() as u64;
+    let base = market.fee_bps as u64;
+
+    let fee_bps = if share >= even {
+        let surcharge = (curve.max_fee_bps as u64 - base) * (share - even) / (BASIS_POINT_DIVIDER - even);
+        base + surcharge
+    } else {
+        let discount = (base - curve.min_fee_bps as u64) * (even - share) / even;
+        base - discount
+    };
+
+    Ok(fee_bps as u16)
+}
+
+/// Fee charged on top of a stake at `fee_bps`
+pub fn bet_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    amount
+        .checked_mul(fee_bps as u64)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_div(BASIS_POINT_DIVIDER)
+        .ok_or_else(|| error!(PredictionMarketError::MathOverflow))
+}
+
+/// Add a fee to the market, splitting it between the treasury, the referrer
+/// if the bet was referred, and the creator. Returns the referrer's cut.
+pub fn accrue_fee(
+    market: &mut Market,
+    config: &ProtocolConfig,
+    fee: u64,
+    referred: bool,
+) -> Result<u64> {
+    let protocol_cut = bet_fee(fee, config.protocol_share_bps)?;
+    let referrer_cut = if referred {
+        bet_fee(fee, config.referrer_share_bps)?
+    } else {
+        0
+    };
+    let creator_cut = fee - protocol_cut - referrer_cut;
+
+    market.fees_collected = market.fees_collected
+        .checked_add(fee)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.protocol_fees = market.protocol_fees
+        .checked_add(protocol_cut)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.referrer_fees = market.referrer_fees
+        .checked_add(referrer_cut)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.creator_fees = market.creator_fees
+        .checked_add(creator_cut)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    Ok(referrer_cut)
+}
+
+/// How one slice of every stake is settled
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SliceResult {
+    /// The slice of the whole pot goes to the backers of this outcome
+    Win(usize),
+    /// Every outcome gets its own slice back
+    Push,
+}
+
+/// Give the whole pot to the backers of `winner`, or fall back to a refund
+/// settlement when nobody backed it. Returns true if the market is refunding.
+pub fn settle_winner_takes_all(market: &mut Market, winner: usize) -> Result<bool> {
+    settle_split(market, &[SliceResult::Win(winner)])
+}
+
+/// Split every pool into equal slices and settle each slice on its own, e.g.
+/// the two half-stakes of an Asian quarter line. A slice won by an outcome
+/// nobody backed is pushed. Returns true if every slice ended up pushed.
+pub fn settle_split(market: &mut Market, slices: &[SliceResult]) -> Result<bool> {
+    let outcome_count = market.pools.len();
+    let slice_count = slices.len() as u64;
+    let mut payouts = vec![0u64; outcome_count];
+    let mut slice_winners = Vec::with_capacity(slices.len());
+
+    for (slice_index, slice) in slices.iter().enumerate() {
+        // Exact partition of each pool so no stake is lost to rounding
+        let slice_index = slice_index as u64;
+        let slice_pools = market.pools
+            .iter()
+            .map(|pool| {
+                let end = (*pool as u128 * (slice_index as u128 + 1) / slice_count as u128) as u64;
+                let start = (*pool as u128 * slice_index as u128 / slice_count as u128) as u64;
+                end - start
+            })
+            .collect::<Vec<u64>>();
+
+        match slice {
+            SliceResult::Win(outcome) if slice_pools[*outcome] > 0 => {
+                payouts[*outcome] = payouts[*outcome]
+                    .checked_add(total_of(&slice_pools)?)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+                slice_winners.push(Some(*outcome));
+            },
+            _ => {
+                for (payout, slice_pool) in payouts.iter_mut().zip(slice_pools.iter()) {
+                    *payout = payout
+                        .checked_add(*slice_pool)
+                        .ok_or(PredictionMarketError::MathOverflow)?;
+                }
+                slice_winners.push(None);
+            },
+        }
+    }
+
+    let refunded = slice_winners.iter().all(Option::is_none);
+    // Only a clean win on every slice has a single winning outcome
+    let first_winner = slice_winners.first().copied().flatten();
+    let single_winner = slice_winners.iter().all(|winner| *winner == first_winner);
+
+    market.payouts = payouts;
+    market.refund_mode = refunded;
+    market.winning_outcome = first_winner
+        .filter(|_| single_winner)
+        .map(|outcome| outcome as u8);
+
+    Ok(refunded)
+}
+
+/// Split the whole pot between `long` and `short` in the ratio `numerator / denominator`.
+/// Without backers on both sides there is nobody to settle against, so it refunds.
+pub fn settle_long_short(
+    market: &mut Market,
+    long: usize,
+    short: usize,
+    numerator: u64,
+    denominator: u64,
+) -> Result<bool> {
+    if market.pools[long] == 0 || market.pools[short] == 0 {
+        settle_refund(market);
+        return Ok(true);
+    }
+
+    let total_pool = total_of(&market.pools)?;
+    let long_payout = (total_pool as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_div(denominator as u128)
+        .ok_or(PredictionMarketError::MathOverflow)? as u64;
+
+    let mut payouts = vec![0u64; market.pools.len()];
+    payouts[long] = long_payout;
+    payouts[short] = total_pool
+        .checked_sub(long_payout)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    market.winning_outcome = if numerator == denominator {
+        Some(long as u8)
+    } else if numerator == 0 {
+        Some(short as u8)
+    } else {
+        None
+    };
+    market.payouts = payouts;
+    market.refund_mode = false;
+
+    Ok(false)
+}
+
+/// Each share of `winner` is redeemed for one token, used by LMSR markets
+/// where the maker rather than the losing side funds the payout
+pub fn settle_shares(market: &mut Market, winner: usize) -> Result<bool> {
+    let mut payouts = vec![0u64; market.pools.len()];
+    payouts[winner] = market.pools[winner];
+
+    market.payouts = payouts;
+    market.refund_mode = false;
+    market.winning_outcome = Some(winner as u8);
+
+    Ok(false)
+}
+
+/// The house pays every bet on `winner` at the odds it was struck at
+pub fn settle_fixed_odds(market: &mut Market, winner: usize) -> Result<bool> {
+    let mut payouts = vec![0u64; market.pools.len()];
+    payouts[winner] = market.liabilities[winner];
+
+    market.payouts = payouts;
+    market.refund_mode = false;
+    market.winning_outcome = Some(winner as u8);
+
+    Ok(false)
+}
+
+/// Amount owed to a position in a settled fixed-odds market
+pub fn fixed_odds_payout(market: &Market, position: &Position) -> Result<u64> {
+    if market.refund_mode {
+        return total_of(&position.amounts);
+    }
+
+    position.bets
+        .iter()
+        .filter(|bet| market.winning_outcome == Some(bet.outcome))
+        .try_fold(0u64, |total, bet| {
+            total
+                .checked_add(apply_odds(bet.stake, bet.odds)?)
+                .ok_or_else(|| error!(PredictionMarketError::MathOverflow))
+        })
+}
+
+/// House liquidity a fixed-odds market must keep for its bettors: the worst
+/// case liability while open, what is actually owed once settled
+pub fn house_reserved(market: &Market) -> Result<u64> {
+    if market.voided || market.refund_mode {
+        total_of(&market.pools)
+    } else if market.resolved {
+        total_of(&market.payouts)
+    } else {
+        Ok(market.liabilities.iter().copied().max().unwrap_or(0))
+    }
+}
+
+/// Every outcome's pool goes back to its own backers
+pub fn settle_refund(market: &mut Market) {
+    market.payouts = market.pools.clone();
+    market.refund_mode = true;
+}
+
+/// Amount owed to a position once its market has been settled
+pub fn position_payout(market: &Market, position: &Position) -> Result<u64> {
+    // A time-weighted pot is shared out by shares, but a refund returns stakes
+    let (holdings, pools) = if market.time_weighting.is_some() && !market.refund_mode {
+        (&position.shares, &market.shares)
+    } else {
+        (&position.amounts, &market.pools)
+    };
+
+    let mut total: u64 = 0;
+
+    for (index, amount) in holdings.iter().enumerate() {
+        let payout = market.payouts.get(index).copied().unwrap_or(0);
+        if *amount == 0 || payout == 0 {
+            continue;
+        }
+
+        let pool = pools[index];
+        require!(pool > 0, PredictionMarketError::EmptyWinningPool);
+
+        total = total
+            .checked_add(pro_rata_share(*amount, payout, pool)?)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+
+    Ok(total)
+}
+
+/// Pro-rata share of an outcome's payout, widened to avoid overflow
+pub fn pro_rata_share(amount: u64, payout: u64, pool: u64) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(payout as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_div(pool as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    u64::try_from(share).map_err(|_| error!(PredictionMarketError::MathOverflow))
+}
+
+/// What a bet of `amount` on `outcome` would be paid if it won with the pools
+/// as they stand after it, or at the odds it locks in on a fixed-odds market
+pub fn projected_payout(market: &Market, outcome: usize, amount: u64, now: i64) -> Result<u64> {
+    if market.pricing == PricingMode::FixedOdds {
+        return apply_odds(amount, market.fixed_odds[outcome]);
+    }
+
+    let pot = total_of(&market.pools)?
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    if market.time_weighting.is_some() {
+        let shares = bet_shares(market, amount, now)?;
+        let pool = market.shares[outcome]
+            .checked_add(shares)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        return pro_rata_share(shares, pot, pool);
+    }
+
+    let pool = market.pools[outcome]
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    pro_rata_share(amount, pot, pool)
+}
+
+/// Shares a stake placed at `now` earns, the stake itself unless the market
+/// is time-weighted
+pub fn bet_shares(market: &Market, amount: u64, now: i64) -> Result<u64> {
+    let Some(weighting) = market.time_weighting else {
+        return Ok(amount);
+    };
+
+    let early = market
+        .start_time
+        .saturating_sub(now)
+        .clamp(0, weighting.ramp_seconds);
+    let bonus_bps = weighting.max_bonus_bps as u64 * early as u64 / weighting.ramp_seconds as u64;
+
+    pro_rata_share(amount, BASIS_POINT_DIVIDER + bonus_bps, BASIS_POINT_DIVIDER)
+}
+
+/// Chance of each outcome implied by the current pools, or by the published
+/// odds of a fixed-odds market, in basis points. An empty pot implies an even
+/// chance for every outcome.
+pub fn implied_probabilities(market: &Market) -> Result<Vec<u64>> {
+    if market.pricing == PricingMode::FixedOdds {
+        return market
+            .fixed_odds
+            .iter()
+            .map(|odds| pro_rata_share(BASIS_POINT_DIVIDER, ODDS_SCALE, *odds))
+            .collect();
+    }
+
+    let pot = total_of(&market.pools)?;
+    if pot == 0 {
+        let even = BASIS_POINT_DIVIDER / market.pools.len() as u64;
+        return Ok(vec![even; market.pools.len()]);
+    }
+
+    market
+        .pools
+        .iter()
+        .map(|pool| pro_rata_share(*pool, BASIS_POINT_DIVIDER, pot))
+        .collect()
+}
+
+/// Decimal odds implied by the current pools for a bet on `outcome`, scaled by ODDS_SCALE
+pub fn implied_odds(market: &Market, outcome: usize) -> Result<u64> {
+    let pool = market.pools[outcome];
+    require!(pool > 0, PredictionMarketError::LegNotPriceable);
+
+    let odds = (total_of(&market.pools)? as u128)
+        .checked_mul(ODDS_SCALE as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_div(pool as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    u64::try_from(odds).map_err(|_| error!(PredictionMarketError::MathOverflow))
+}
+
+/// Odds a parlay leg locks in: the implied odds less the parlay pool's margin,
+/// never below evens. Markets with less than the pool's minimum staked are
+/// too easy to move to be priced.
+pub fn parlay_leg_odds(market: &Market, outcome: usize, parlay_pool: &ParlayPool) -> Result<u64> {
+    require!(
+        total_of(&market.pools)? >= parlay_pool.min_leg_pool,
+        PredictionMarketError::LegNotPriceable
+    );
+
+    let odds = pro_rata_share(
+        implied_odds(market, outcome)?,
+        BASIS_POINT_DIVIDER - parlay_pool.margin_bps as u64,
+        BASIS_POINT_DIVIDER,
+    )?;
+
+    Ok(odds.max(ODDS_SCALE))
+}
+
+/// Payout multiple of a bet of `amount` on `outcome` if the pools closed right
+/// after it, or the odds it locks in on a fixed-odds market, in basis points
+/// of the stake
+pub fn projected_payout_bps(market: &Market, outcome: usize, amount: u64, now: i64) -> Result<u64> {
+    if market.pricing == PricingMode::FixedOdds {
+        return Ok(market.fixed_odds[outcome]);
+    }
+
+    pro_rata_share(
+        projected_payout(market, outcome, amount, now)?,
+        BASIS_POINT_DIVIDER,
+        amount,
+    )
+}
+
+/// How a parlay leg on `outcome` settles once its market is final, and the
+/// factor (scaled by ODDS_SCALE) the ticket payout is multiplied by
+pub fn parlay_leg_result(market: &Market, outcome: usize, locked_odds: u64) -> Result<(LegStatus, u64)> {
+    if market.voided || market.refund_mode {
+        return Ok((LegStatus::Void, ODDS_SCALE));
+    }
+
+    let payout = market.payouts.get(outcome).copied().unwrap_or(0);
+    if payout == 0 {
+        return Ok((LegStatus::Lost, 0));
+    }
+
+    if market.winning_outcome == Some(outcome as u8) {
+        return Ok((LegStatus::Won, locked_odds));
+    }
+
+    // Split settlement: the realised multiple, never more than the odds the
+    // pool reserved for
+    let realised = (payout as u128)
+        .checked_mul(ODDS_SCALE as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        .checked_div(market.pools[outcome] as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+
+    Ok((LegStatus::Partial, realised.min(locked_odds as u128) as u64))
+}
+
+/// Stake multiplied by the factor of every settled leg. Applied in leg order,
+/// the way the ticket's max payout was, so it can never round above it
+/// whichever order the legs settle in.
+pub fn parlay_payout(stake: u64, legs: &[ParlayLeg]) -> Result<u64> {
+    legs.iter()
+        .filter(|leg| leg.status != LegStatus::Pending)
+        .try_fold(stake, |payout, leg| apply_odds(payout, leg.factor))
+}
+
+/// Multiply an amount by a factor scaled by ODDS_SCALE
+pub fn apply_odds(amount: u64, odds: u64) -> Result<u64> {
+    let result = (amount as u128)
+        .checked_mul(odds as u128)
+        .ok_or(PredictionMarketError::MathOverflow)?
+        / ODDS_SCALE as u128;
+
+    u64::try_from(result).map_err(|_| error!(PredictionMarketError::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAKE: u64 = 1_140_891;
+
+    fn leg(odds: u64) -> ParlayLeg {
+        ParlayLeg {
+            market: Pubkey::new_unique(),
+            outcome: 0,
+            odds,
+            status: LegStatus::Pending,
+            factor: 0,
+        }
+    }
+
+    fn settle(legs: &mut [ParlayLeg], index: usize) -> u64 {
+        legs[index].status = LegStatus::Won;
+        legs[index].factor = legs[index].odds;
+        parlay_payout(STAKE, legs).unwrap()
+    }
+
+    #[test]
+    fn parlay_payout_does_not_depend_on_settle_order() {
+        // Applied in the order [2, 0, 1] these odds truncate to 8 more than the max
+        let mut legs = vec![leg(28_652), leg(37_769), leg(36_289)];
+        let max_payout = legs
+            .iter()
+            .try_fold(STAKE, |payout, leg| apply_odds(payout, leg.odds))
+            .unwrap();
+
+        let mut payout = 0;
+        for index in [2, 0, 1] {
+            payout = settle(&mut legs, index);
+            assert!(payout <= max_payout);
+        }
+        assert_eq!(payout, max_payout);
+    }
+
+    #[test]
+    fn void_legs_leave_the_parlay_payout_unchanged() {
+        let mut legs = vec![leg(20_000), leg(30_000)];
+        legs[0].status = LegStatus::Void;
+        legs[0].factor = ODDS_SCALE;
+
+        assert_eq!(parlay_payout(STAKE, &legs).unwrap(), STAKE);
+        assert_eq!(settle(&mut legs, 1), apply_odds(STAKE, 30_000).unwrap());
+    }
+}
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+/// Enough for a 0-0 to 5-5 correct score grid plus an "any other" bucket
+pub const MAX_OUTCOMES: usize = 37;
+pub const MAX_OUTCOME_LABEL_LEN: usize = 50;
+pub const MAX_CATEGORY_LEN: usize = 32;
+
 #[account]
 #[derive(InitSpace)]
 pub struct Market{
@@ -15,19 +20,433 @@ pub struct Market{
     pub start_time: i64,
     pub end_time: i64,
     pub resolution_time: i64,
-    pub yes_pool: u64,
-    pub no_pool: u64,
-    pub draw_pool: u64,
+    pub market_type: MarketType,
+    #[max_len(MAX_OUTCOMES, MAX_OUTCOME_LABEL_LEN)]
+    pub outcome_labels: Vec<String>,
+    /// Total staked on each outcome, indexed like `outcome_labels`.
+    /// Outstanding shares per outcome in an LMSR market.
+    #[max_len(MAX_OUTCOMES)]
+    pub pools: Vec<u64>,
+    /// Share of the pot owed to the backers of each outcome, set on resolution
+    #[max_len(MAX_OUTCOMES)]
+    pub payouts: Vec<u64>,
     pub resolved: bool,
     pub outcome: Option<bool>,
     pub is_draw: bool,
+    pub winning_outcome: Option<u8>,
     pub oracle_feed: [u8; 32],
     pub vault: Pubkey,
     pub final_result_value: i64,
     pub bump: u8,
-    pub fees_collected: u64
+    pub fees_collected: u64,
+    pub voided: bool,
+    pub fees_withdrawn: u64,
+    pub refund_mode: bool,
+    /// Free-form grouping for generic markets (e.g. "elections"), empty otherwise
+    #[max_len(MAX_CATEGORY_LEN)]
+    pub category: String,
+    /// Conditional markets only settle if this market resolves to `parent_outcome`
+    pub parent_market: Option<Pubkey>,
+    pub parent_outcome: u8,
+    pub pricing: PricingMode,
+    /// Tokens in the vault backing outstanding AMM shares, fees excluded
+    pub collateral: u64,
+    /// Outcome shares held by the constant-product pool, indexed like `pools`
+    #[max_len(MAX_OUTCOMES)]
+    pub reserves: Vec<u64>,
+    /// LP shares issued against the constant-product pool
+    pub lp_supply: u64,
+    /// Published decimal odds per outcome of a fixed-odds market, scaled by ODDS_SCALE
+    #[max_len(MAX_OUTCOMES)]
+    pub fixed_odds: Vec<u64>,
+    /// Most the house may lose on each outcome of a fixed-odds market
+    #[max_len(MAX_OUTCOMES)]
+    pub exposure_caps: Vec<u64>,
+    /// Total owed to bettors if each outcome wins at the odds they locked in
+    #[max_len(MAX_OUTCOMES)]
+    pub liabilities: Vec<u64>,
+    /// Fee charged on top of every stake in basis points, before volume discounts
+    pub fee_bps: u16,
+    /// Unclaimed shares of `fees_collected` owed to the treasury, the creator
+    /// and all referrers together
+    pub protocol_fees: u64,
+    pub creator_fees: u64,
+    pub referrer_fees: u64,
+    /// Seconds before `start_time` that betting closes, 0 to take bets until the start
+    pub betting_cutoff: i64,
+    /// Scales the fee of each bet with how lopsided it leaves the pools, flat `fee_bps` if unset
+    pub fee_curve: Option<FeeCurve>,
+    /// Rewards early stakes with extra shares of their outcome, parimutuel markets only
+    pub time_weighting: Option<TimeWeighting>,
+    /// Shares issued on each outcome of a time-weighted market, indexed like `pools`
+    #[max_len(MAX_OUTCOMES)]
+    pub shares: Vec<u64>,
+    /// Where the result comes from, `oracle_feed` is the Switchboard feed hash
+    pub oracle_source: OracleSource,
+    /// Switchboard feed hashes a quorum market settles from
+    #[max_len(MAX_QUORUM_FEEDS)]
+    pub quorum_feeds: Vec<[u8; 32]>,
+    /// Last result read from each quorum feed, `None` if it had none to give
+    #[max_len(MAX_QUORUM_FEEDS)]
+    pub oracle_reports: Vec<Option<i64>>,
+    /// The quorum feeds disagreed on the result, resolution can be retried
+    pub disputed: bool,
+    /// An optimistic proposal is awaiting settlement, the oracle source is locked
+    pub proposal_open: bool,
+    /// Checks every Switchboard result must pass before the market settles on it
+    pub feed_checks: Option<FeedChecks>,
+    /// Seconds past `resolution_time` the oracle gets before the authority
+    /// may announce a fallback result
+    pub fallback_grace_period: i64,
+    /// Fallback result announced by the authority, waiting out its timelock
+    pub fallback: Option<FallbackResolution>,
+}
+
+impl Market {
+    /// A parimutuel market with empty pools and every setting at its default,
+    /// creators override the fields their kind of market needs
+    pub fn new(
+        authority: Pubkey,
+        question: String,
+        game_key: String,
+        market_type: MarketType,
+        outcome_labels: Vec<String>,
+        start_time: i64,
+        end_time: i64,
+        resolution_time: i64,
+        oracle_feed: [u8; 32],
+        fee_bps: u16,
+        vault: Pubkey,
+        bump: u8,
+    ) -> Self {
+        Self {
+            authority,
+            question,
+            home_team: String::new(),
+            away_team: String::new(),
+            game_key,
+            start_time,
+            end_time,
+            resolution_time,
+            market_type,
+            pools: vec![0; outcome_labels.len()],
+            outcome_labels,
+            payouts: vec![],
+            resolved: false,
+            outcome: None,
+            is_draw: false,
+            winning_outcome: None,
+            oracle_feed,
+            vault,
+            final_result_value: 0,
+            bump,
+            fees_collected: 0,
+            voided: false,
+            fees_withdrawn: 0,
+            refund_mode: false,
+            category: String::new(),
+            parent_market: None,
+            parent_outcome: 0,
+            pricing: PricingMode::Parimutuel,
+            collateral: 0,
+            reserves: vec![],
+            lp_supply: 0,
+            fixed_odds: vec![],
+            exposure_caps: vec![],
+            liabilities: vec![],
+            fee_bps,
+            protocol_fees: 0,
+            creator_fees: 0,
+            referrer_fees: 0,
+            betting_cutoff: 0,
+            fee_curve: None,
+            time_weighting: None,
+            shares: vec![],
+            oracle_source: OracleSource::Switchboard,
+            quorum_feeds: vec![],
+            oracle_reports: vec![],
+            disputed: false,
+            proposal_open: false,
+            feed_checks: None,
+            fallback_grace_period: DEFAULT_FALLBACK_GRACE_PERIOD,
+            fallback: None,
+        }
+    }
+
+    /// When the late-betting cutoff window begins
+    pub fn betting_closes_at(&self) -> i64 {
+        self.start_time.saturating_sub(self.betting_cutoff)
+    }
+
+    /// Whether anyone has money riding on the market
+    pub fn has_stakes(&self) -> bool {
+        self.collateral > 0 || self.pools.iter().any(|pool| *pool > 0)
+    }
+
+    /// Where the result comes from is frozen once the market starts or takes
+    /// its first stake, so it cannot be picked after seeing how things went
+    pub fn oracle_locked(&self, now: i64) -> bool {
+        now >= self.start_time || self.has_stakes()
+    }
+
+    /// Whether the market settled with `outcome` as its single winner
+    pub fn resolved_to(&self, outcome: u8) -> bool {
+        self.resolved && !self.voided && !self.refund_mode && self.winning_outcome == Some(outcome)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum OracleSource {
+    /// Switchboard On-Demand pull feed whose hash is the market's `oracle_feed`
+    Switchboard,
+    /// Pyth-style price account publishing the result as its price
+    PriceAccount { account: Pubkey },
+    /// Result signed by a sports-data signer registered with the protocol
+    SignedAttestation { signer: Pubkey },
+    /// At least `threshold` of the market's `quorum_feeds` report the same result
+    Quorum { threshold: u8 },
+    /// Anyone may propose the result by posting `bond`, which stands unless
+    /// it is disputed with a matching bond within `challenge_seconds`.
+    /// Disputes are settled by `arbiter` or the market's Switchboard feed.
+    Optimistic { bond: u64, challenge_seconds: i64, arbiter: Pubkey },
+}
+
+/// Limits on how fresh and how tightly agreed a Switchboard result must be
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct FeedChecks {
+    /// Most slots the result may lag behind the current slot
+    pub max_staleness_slots: u64,
+    /// Fewest oracle samples the result must be built from
+    pub min_samples: u8,
+    /// Largest standard deviation across the samples, in the feed's units
+    pub max_std_dev: u64,
+}
+
+/// Grace period a market gets unless its authority sets another
+pub const DEFAULT_FALLBACK_GRACE_PERIOD: i64 = 7 * 86_400;
+/// Time bettors get to see an announced fallback result before it settles the market
+pub const FALLBACK_TIMELOCK: i64 = 2 * 86_400;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct FallbackResolution {
+    pub result: i64,
+    pub executable_at: i64,
+}
+
+/// Most Switchboard feeds a quorum market can poll
+pub const MAX_QUORUM_FEEDS: usize = 5;
+
+/// Result proposed for an optimistic market, with the bonds posted on it
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub result: i64,
+    /// Posted by the proposer and matched by a disputer
+    pub bond: u64,
+    /// Last moment the proposal can be disputed
+    pub challenge_ends_at: i64,
+    pub disputer: Option<Pubkey>,
+    pub status: ProposalStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ProposalStatus {
+    Proposed,
+    Disputed,
+    /// Bonds have been paid out
+    Settled,
+}
+
+/// A sports-data provider whose signed results can settle markets
+#[account]
+#[derive(InitSpace)]
+pub struct OracleSigner {
+    pub signer: Pubkey,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum MarketType {
+    /// Football 1X2 market, outcomes follow the oracle codes: 0 = away, 1 = home, 2 = draw
+    MatchResult,
+    /// Any number of labelled outcomes, the oracle reports the winning index
+    Categorical,
+    /// Over/Under on total goals, `line` is in hundredths of a goal (250 = 2.5)
+    TotalGoals { line: i32 },
+    /// Asian handicap applied to the home side, `line` in hundredths of a goal (-150 = Home -1.5)
+    AsianHandicap { line: i32 },
+    /// Every score from 0-0 to `max_goals`-`max_goals` plus a final "any other" bucket
+    CorrectScore { max_goals: u8 },
+    /// Long/short split of the pot depending on where the oracle value lands between the bounds
+    Scalar { lower: i64, upper: i64 },
+}
+
+/// How bets on a market are priced
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PricingMode {
+    /// Stakes are pooled per outcome and the pot is split at resolution
+    Parimutuel,
+    /// Shares are bought from a logarithmic market scoring rule maker with
+    /// liquidity parameter `liquidity`, each winning share pays out one token
+    Lmsr { liquidity: u64 },
+    /// Outcome shares are swapped against a constant-product pool funded by
+    /// liquidity providers, who earn `fee_bps` of every swap
+    ConstantProduct { fee_bps: u16 },
+    /// Back and lay limit orders are matched peer to peer on an `OrderBook`,
+    /// each matched bet mints shares that pay out one token if they win
+    Exchange,
+    /// The authority publishes decimal odds per outcome and the house
+    /// liquidity in the market vault pays every winning bet at its locked odds
+    FixedOdds,
+}
+
+/// Highest swap fee a constant-product market can charge its traders
+pub const MAX_LP_FEE_BPS: u16 = 1_000;
+/// Largest liquidity parameter an LMSR market can be created with (1M tokens)
+pub const MAX_LMSR_LIQUIDITY: u64 = 1_000_000_000_000;
+/// Outstanding shares of an LMSR outcome are capped at this multiple of the
+/// liquidity parameter, which keeps the cost function well within the
+/// precision of its floating point maths
+pub const MAX_LMSR_SHARES_PER_LIQUIDITY: u64 = 100;
+
+impl MarketType {
+    /// Extra PDA seed so several football markets can share one `game_key`.
+    /// Empty for match result markets, which keeps their original address.
+    pub fn seed_suffix(&self) -> Vec<u8> {
+        match self {
+            MarketType::MatchResult | MarketType::Categorical | MarketType::Scalar { .. } => vec![],
+            MarketType::TotalGoals { line } => [b"ou".as_ref(), &line.to_le_bytes()].concat(),
+            MarketType::AsianHandicap { line } => [b"ah".as_ref(), &line.to_le_bytes()].concat(),
+            MarketType::CorrectScore { max_goals } => [b"cs".as_ref(), &[*max_goals]].concat(),
+        }
+    }
+}
+
+pub const BASIS_POINT_DIVIDER: u64 = 10_000;
+/// Highest fee a market can be created with (5%)
+pub const MAX_FEE_BPS: u16 = 500;
+
+/// Volume tiers as (cumulative stake, share of the market fee still charged
+/// in basis points), highest threshold last
+pub const VOLUME_FEE_TIERS: [(u64, u64); 3] = [
+    (1_000_000_000, 9_000),
+    (10_000_000_000, 7_500),
+    (100_000_000_000, 5_000),
+];
+
+/// Fee range of a market whose fee follows pool imbalance. A bet that leaves
+/// its outcome with an even share of the pot pays the market's `fee_bps`,
+/// moving linearly to `max_fee_bps` as the outcome takes the whole pot and to
+/// `min_fee_bps` as it is left with none.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct FeeCurve {
+    pub min_fee_bps: u16,
+    pub max_fee_bps: u16,
+}
+
+/// Highest bonus a time-weighted market can pay its earliest stakes (2x shares)
+pub const MAX_EARLY_BONUS_BPS: u16 = 10_000;
+
+/// A stake placed `ramp_seconds` or more before `start_time` earns
+/// `max_bonus_bps` extra shares per token, falling linearly to no bonus for a
+/// stake placed right at the start
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct TimeWeighting {
+    pub max_bonus_bps: u16,
+    pub ramp_seconds: i64,
+}
+
+/// Cumulative stake of one bettor across every market, used for fee tiers
+#[account]
+#[derive(InitSpace)]
+pub struct UserVolume {
+    pub user: Pubkey,
+    pub cumulative_stake: u64,
+    pub bump: u8,
+}
+
+/// Protocol-wide split of every fee between the treasury, a referrer and
+/// the market creator, who keeps whatever is left
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    /// Owner of the token accounts the protocol share is paid out to
+    pub treasury: Pubkey,
+    /// Share of every fee owed to the treasury, in basis points of the fee
+    pub protocol_share_bps: u16,
+    /// Share of a referred bet's fee owed to the referrer, in basis points of the fee
+    pub referrer_share_bps: u16,
+    pub bump: u8,
+}
+
+/// Fees a referrer has earned on one market
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub market: Pubkey,
+    pub referrer: Pubkey,
+    pub fees_owed: u64,
+    pub bump: u8,
+}
+
+/// Lines are stored in hundredths of a goal
+pub const LINE_SCALE: i64 = 100;
+
+/// Render a line stored in hundredths of a goal, e.g. 250 -> "2.5", -125 -> "-1.25"
+pub fn format_line(line: i32) -> String {
+    let sign = if line < 0 { "-" } else { "" };
+    let abs = line.unsigned_abs();
+    let whole = abs / LINE_SCALE as u32;
+    let fraction = abs % LINE_SCALE as u32;
+
+    match fraction {
+        0 => format!("{}{}", sign, whole),
+        f if f % 10 == 0 => format!("{}{}.{}", sign, whole, f / 10),
+        f => format!("{}{}.{:02}", sign, whole, f),
+    }
+}
+
+/// Render a handicap line with an explicit sign, e.g. 25 -> "+0.25"
+pub fn format_handicap(line: i32) -> String {
+    if line > 0 {
+        format!("+{}", format_line(line))
+    } else {
+        format_line(line)
+    }
+}
+
+pub const AWAY_OUTCOME: u8 = 0;
+pub const HOME_OUTCOME: u8 = 1;
+pub const DRAW_OUTCOME: u8 = 2;
+
+pub const OVER_OUTCOME: u8 = 0;
+pub const UNDER_OUTCOME: u8 = 1;
+
+pub const LONG_OUTCOME: u8 = 0;
+pub const SHORT_OUTCOME: u8 = 1;
+
+pub const HANDICAP_HOME_OUTCOME: u8 = 0;
+pub const HANDICAP_AWAY_OUTCOME: u8 = 1;
+
+pub const MAX_CORRECT_SCORE_GOALS: u8 = 5;
+
+/// Outcome index of a score in a correct score market, the last index is "any other"
+pub fn correct_score_outcome(max_goals: u8, home_goals: i64, away_goals: i64) -> usize {
+    let side = max_goals as i64 + 1;
+    if home_goals < side && away_goals < side {
+        (home_goals * side + away_goals) as usize
+    } else {
+        (side * side) as usize
+    }
 }
 
+/// Score feeds encode a final score as `home_goals * SCORE_ENCODING_BASE + away_goals`
+pub const SCORE_ENCODING_BASE: i64 = 1_000;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum BetType {
     Home,
@@ -35,13 +454,176 @@ pub enum BetType {
     Draw,
 }
 
+impl BetType {
+    pub fn outcome_index(&self) -> u8 {
+        match self {
+            BetType::Home => HOME_OUTCOME,
+            BetType::Away => AWAY_OUTCOME,
+            BetType::Draw => DRAW_OUTCOME,
+        }
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Position {
     pub user: Pubkey,
     pub market: Pubkey,
-    pub yes_amount: u64,
-    pub no_amount: u64,
-    pub draw_amount: u64,
+    /// Amount staked on each outcome (shares held in an LMSR market), indexed like `Market::pools`
+    #[max_len(MAX_OUTCOMES)]
+    pub amounts: Vec<u64>,
+    pub fees_paid: u64,
+    /// Share of a constant-product pool's liquidity
+    pub lp_shares: u64,
+    /// Tokens put into matched exchange bets, returned if the market is voided
+    pub exchange_paid: u64,
+    /// Stake counted towards the owner's volume tier, taken back out if it is
+    /// refunded or sold
+    pub volume: u64,
+    /// Every bet on a fixed-odds market with the odds it was struck at
+    #[max_len(MAX_FIXED_ODDS_BETS)]
+    pub bets: Vec<LockedBet>,
+    /// Shares earned on each outcome of a time-weighted market
+    #[max_len(MAX_OUTCOMES)]
+    pub shares: Vec<u64>,
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+pub const MAX_FIXED_ODDS_BETS: usize = 16;
+
+/// What `quote_bet` returns for a stake on one outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BetQuote {
+    /// Rate the bettor would pay after volume discounts
+    pub fee_bps: u16,
+    /// Fee charged on top of the stake
+    pub fee: u64,
+    /// Paid out if the outcome wins and nothing else is staked
+    pub projected_payout: u64,
+    /// Chance of each outcome implied before the bet, in basis points
+    pub implied_probabilities: Vec<u64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct LockedBet {
+    pub outcome: u8,
+    pub stake: u64,
+    /// Decimal odds scaled by ODDS_SCALE
+    pub odds: u64,
+}
+
+pub const MAX_PARLAY_LEGS: usize = 8;
+/// Decimal odds are stored scaled by this factor (25_000 = 2.5)
+pub const ODDS_SCALE: u64 = 10_000;
+/// Probabilities are reported scaled by this factor (5_000 = 50%)
+pub const PRICE_SCALE: u64 = 10_000;
+
+/// House liquidity that underwrites parlay tickets in one mint
+#[account]
+#[derive(InitSpace)]
+pub struct ParlayPool {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    /// Payouts the vault must keep available for open and settled tickets
+    pub reserved: u64,
+    pub max_ticket_payout: u64,
+    /// Smallest total a leg's pools must hold before its odds can be locked in
+    pub min_leg_pool: u64,
+    /// Taken off every leg's implied odds, in basis points
+    pub margin_bps: u16,
+    pub bump: u8,
+}
+
+pub const MAX_PARLAY_MARGIN_BPS: u16 = 2_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum LegStatus {
+    Pending,
+    Won,
+    Lost,
+    /// The leg's market was voided or refunded, it counts as odds of 1.0
+    Void,
+    /// Split or scalar settlement, paid at the realised parimutuel multiple
+    Partial,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct ParlayLeg {
+    pub market: Pubkey,
+    pub outcome: u8,
+    /// Implied parimutuel odds less the pool margin, locked in when the ticket was opened
+    pub odds: u64,
+    pub status: LegStatus,
+    /// Factor the leg settled at, scaled by ODDS_SCALE
+    pub factor: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ParlayStatus {
+    Open,
+    Lost,
+    Settled,
+    Claimed,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Parlay {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub parlay_id: u64,
+    pub stake: u64,
+    /// Payout if every leg wins at its locked odds, reserved in the pool
+    pub max_payout: u64,
+    /// Stake multiplied by the factor of every leg settled so far, in leg order
+    pub payout: u64,
+    #[max_len(MAX_PARLAY_LEGS)]
+    pub legs: Vec<ParlayLeg>,
+    pub status: ParlayStatus,
+    pub bump: u8,
+}
+
+pub const MAX_RESTING_ORDERS: usize = 32;
+/// Resting orders one owner can have on a book at once
+pub const MAX_ORDERS_PER_OWNER: usize = 4;
+/// Smallest backer stake an exchange order can be placed with (1 token)
+pub const MIN_ORDER_STAKE: u64 = 1_000_000;
+/// Highest odds an exchange order can be placed at (1000.0)
+pub const MAX_EXCHANGE_ODDS: u64 = 1_000 * ODDS_SCALE;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum OrderSide {
+    /// Bets on the outcome happening
+    Back,
+    /// Bets against the outcome, risking the backer's winnings
+    Lay,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct Order {
+    pub order_id: u64,
+    pub owner: Pubkey,
+    pub side: OrderSide,
+    pub outcome: u8,
+    /// Decimal odds scaled by ODDS_SCALE
+    pub odds: u64,
+    /// Backer's stake still unmatched, lay orders are sized the same way
+    pub stake: u64,
+    /// Tokens held in the market vault for the unmatched stake, the stake
+    /// itself for a back order and the liability for a lay order
+    pub escrow: u64,
+    /// Protocol fee held for the unmatched part, charged as it fills
+    pub fee_reserved: u64,
+}
+
+/// Resting back/lay orders of an exchange market
+#[account]
+#[derive(InitSpace)]
+pub struct OrderBook {
+    pub market: Pubkey,
+    pub next_order_id: u64,
+    #[max_len(MAX_RESTING_ORDERS)]
+    pub orders: Vec<Order>,
+    pub bump: u8,
+}
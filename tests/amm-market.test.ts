@@ -0,0 +1,188 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+import { getAccount } from "@solana/spl-token";
+
+describe("AMM Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  const marketKey = "AMM_MARKET_001";
+  const lpFeeBps = 200;
+  const liquidity = 100_000_000n;
+  const setSize = 10_000_000n;
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+  });
+
+  it("creates a constant-product market", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+
+    await program.methods
+      .createAmmMarket(
+        "Will the final go to penalties?",
+        marketKey,
+        ["Yes", "No"],
+        lpFeeBps,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.pricing.constantProduct.feeBps, lpFeeBps);
+    assert.deepStrictEqual(market.reserves.map((r) => r.toString()), ["0", "0"]);
+  });
+
+  it("mints LP shares for the first deposit and charges the protocol fee", async () => {
+    await program.methods
+      .addLiquidity(new anchor.BN(liquidity.toString()))
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.lpSupply.toString(), liquidity.toString());
+    assert.strictEqual(
+      market.feesCollected.toString(),
+      (liquidity * BigInt(DEFAULT_FEE_BPS) / 10_000n).toString()
+    );
+    assert.deepStrictEqual(
+      market.reserves.map((r) => r.toString()),
+      [liquidity.toString(), liquidity.toString()]
+    );
+
+    const [positionPda] = derivePositionPda(program.programId, marketPda, context.user1.publicKey);
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.lpShares.toString(), liquidity.toString());
+  });
+
+  it("mints complete sets and charges the protocol fee", async () => {
+    await program.methods
+      .mintCompleteSet(new anchor.BN(setSize.toString()))
+      .accountsPartial({
+        market: marketPda,
+        user: context.user2.publicKey,
+        userTokenAccount: context.user2TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user2])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    const expectedFee = (liquidity + setSize) * BigInt(DEFAULT_FEE_BPS) / 10_000n;
+    assert.strictEqual(market.feesCollected.toString(), expectedFee.toString());
+    assert.strictEqual(market.pools[0].toString(), (liquidity + setSize).toString());
+  });
+
+  it("swaps along the constant-product curve and leaves the LP fee in the pool", async () => {
+    const [positionPda] = derivePositionPda(program.programId, marketPda, context.user2.publicKey);
+
+    try {
+      await program.methods
+        .swapOutcomes(1, 0, new anchor.BN(setSize.toString()), new anchor.BN(setSize.toString()))
+        .accountsPartial({
+          market: marketPda,
+          position: positionPda,
+          user: context.user2.publicKey,
+        } as any)
+        .signers([context.user2])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("SlippageExceeded"));
+    }
+
+    await program.methods
+      .swapOutcomes(1, 0, new anchor.BN(setSize.toString()), new anchor.BN(0))
+      .accountsPartial({
+        market: marketPda,
+        position: positionPda,
+        user: context.user2.publicKey,
+      } as any)
+      .signers([context.user2])
+      .rpc();
+
+    const fee = setSize * BigInt(lpFeeBps) / 10_000n;
+    const product = liquidity * liquidity;
+    const newReserveIn = liquidity + setSize - fee;
+    const newReserveOut = (product + newReserveIn - 1n) / newReserveIn;
+    const amountOut = liquidity - newReserveOut;
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.reserves[0].toString(), (liquidity - amountOut).toString());
+    assert.strictEqual(market.reserves[1].toString(), (liquidity + setSize).toString());
+
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.amounts[0].toString(), (setSize + amountOut).toString());
+    assert.strictEqual(position.amounts[1].toString(), "0");
+  });
+
+  it("returns merged tokens and the leftover shares when liquidity is removed", async () => {
+    const [positionPda] = derivePositionPda(program.programId, marketPda, context.user1.publicKey);
+    const before = await program.account.market.fetch(marketPda);
+    const reserves = before.reserves.map((r) => BigInt(r.toString()));
+    const merged = reserves[0] < reserves[1] ? reserves[0] : reserves[1];
+
+    const balanceBefore = await getAccount(context.provider.connection, context.user1TokenAccount);
+
+    await program.methods
+      .removeLiquidity(new anchor.BN(liquidity.toString()))
+      .accountsPartial({
+        market: marketPda,
+        position: positionPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const balanceAfter = await getAccount(context.provider.connection, context.user1TokenAccount);
+    assert.strictEqual((balanceAfter.amount - balanceBefore.amount).toString(), merged.toString());
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.lpSupply.toString(), "0");
+    assert.deepStrictEqual(market.reserves.map((r) => r.toString()), ["0", "0"]);
+
+    // The LP fee earned on the swap stays with the provider as outcome shares
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.lpShares.toString(), "0");
+    assert.strictEqual(position.amounts[1].toString(), (reserves[1] - merged).toString());
+  });
+});
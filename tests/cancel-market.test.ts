@@ -0,0 +1,214 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveMarketPda,
+  deriveVaultPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+  PROTOCOL_SHARE_BPS,
+} from "./utils";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccount,
+} from "@solana/spl-token";
+
+describe("Cancel Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let authorityTokenAccount: PublicKey;
+
+  async function createMarket(gameKey: string): Promise<PublicKey> {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    const oracleFeedHash = generateOracleFeedHash();
+
+    await program.methods
+      .createFootballMarket(
+        "Cancel market test",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const [marketPda] = deriveMarketPda(program.programId, gameKey);
+    return marketPda;
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    authorityTokenAccount = getAssociatedTokenAddressSync(
+      context.mint,
+      context.authority.publicKey
+    );
+
+    // Create the associated token account if it doesn't exist
+    try {
+      await createAssociatedTokenAccount(
+        context.provider.connection,
+        context.authority,
+        context.mint,
+        context.authority.publicKey
+      );
+    } catch (error) {
+      // Account might already exist, that's okay
+    }
+  });
+
+  it("voids a market and returns already collected fees to the vault", async () => {
+    const marketPda = await createMarket("GAME_CANCEL_001");
+    const [vaultPda] = deriveVaultPda(program.programId, marketPda);
+
+    const betAmount = new anchor.BN(100_000_000); // 100 tokens
+    const expectedFee = betAmount.mul(new anchor.BN(50)).div(new anchor.BN(10_000));
+
+    await program.methods
+      .placeBetOnMarket(betAmount, { home: {} }, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    // Sweep the fees before the fixture is abandoned
+    await program.methods
+      .collectFeesFromMarket()
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+        feeReceiver: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const swept = await program.account.market.fetch(marketPda);
+    // Only the creator's share can be swept before the market settles
+    const creatorFee = expectedFee.sub(
+      expectedFee.mul(new anchor.BN(PROTOCOL_SHARE_BPS)).div(new anchor.BN(10_000))
+    );
+    assert.strictEqual(swept.feesWithdrawn.toString(), creatorFee.toString());
+
+    await program.methods
+      .cancelMarket()
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.voided, true);
+    assert.strictEqual(market.resolved, false);
+    assert.strictEqual(market.feesWithdrawn.toString(), "0");
+    assert.strictEqual(market.feesCollected.toString(), expectedFee.toString());
+
+    // The vault holds every stake and every fee again
+    const vault = await getAccount(context.provider.connection, vaultPda);
+    assert.strictEqual(
+      vault.amount.toString(),
+      betAmount.add(expectedFee).toString()
+    );
+  });
+
+  it("fails when not the market authority", async () => {
+    const marketPda = await createMarket("GAME_CANCEL_UNAUTHORIZED");
+
+    try {
+      await program.methods
+        .cancelMarket()
+        .accounts({
+          market: marketPda,
+          authority: context.user1.publicKey,
+          authorityTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("UnauthorizedCanceller") ||
+        error.message.includes("2006")
+      );
+    }
+  });
+
+  it("rejects bets and a second cancellation once voided", async () => {
+    const marketPda = await createMarket("GAME_CANCEL_TWICE");
+
+    await program.methods
+      .cancelMarket()
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    try {
+      await program.methods
+        .placeBetOnMarket(new anchor.BN(100_000_000), { home: {} }, null)
+        .accountsPartial({
+          market: marketPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("MarketVoided"));
+    }
+
+    try {
+      await program.methods
+        .cancelMarket()
+        .accounts({
+          market: marketPda,
+          authority: context.authority.publicKey,
+          authorityTokenAccount: authorityTokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("MarketVoided"));
+    }
+  });
+});
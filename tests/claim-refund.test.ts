@@ -0,0 +1,182 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveMarketPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  AWAY_OUTCOME,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccount,
+} from "@solana/spl-token";
+
+describe("Claim Refund", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  let positionPda: PublicKey;
+  let authorityTokenAccount: PublicKey;
+  const gameKey = "GAME_REFUND_001";
+  const betAmount = new anchor.BN(100_000_000); // 100 tokens
+  const expectedFee = betAmount.mul(new anchor.BN(50)).div(new anchor.BN(10_000));
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    authorityTokenAccount = getAssociatedTokenAddressSync(
+      context.mint,
+      context.authority.publicKey
+    );
+
+    // Create the associated token account if it doesn't exist
+    try {
+      await createAssociatedTokenAccount(
+        context.provider.connection,
+        context.authority,
+        context.mint,
+        context.authority.publicKey
+      );
+    } catch (error) {
+      // Account might already exist, that's okay
+    }
+
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    const oracleFeedHash = generateOracleFeedHash();
+
+    await program.methods
+      .createFootballMarket(
+        "Claim refund test",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveMarketPda(program.programId, gameKey);
+    [positionPda] = derivePositionPda(
+      program.programId,
+      marketPda,
+      context.user1.publicKey
+    );
+
+    await program.methods
+      .placeBetOnMarket(betAmount, { away: {} }, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+  });
+
+  it("fails when market is not voided", async () => {
+    try {
+      await program.methods
+        .claimRefundFromMarket()
+        .accountsPartial({
+          market: marketPda,
+          position: positionPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("MarketNotVoided"));
+    }
+  });
+
+  it("refunds the full stake and fee once the market is voided", async () => {
+    await program.methods
+      .cancelMarket()
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const userBefore = await getAccount(
+      context.provider.connection,
+      context.user1TokenAccount
+    );
+
+    await program.methods
+      .claimRefundFromMarket()
+      .accountsPartial({
+        market: marketPda,
+        position: positionPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const userAfter = await getAccount(
+      context.provider.connection,
+      context.user1TokenAccount
+    );
+    assert.strictEqual(
+      (userAfter.amount - userBefore.amount).toString(),
+      betAmount.add(expectedFee).toString()
+    );
+
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.amounts[AWAY_OUTCOME].toString(), "0");
+    assert.strictEqual(position.feesPaid.toString(), "0");
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.feesCollected.toString(), "0");
+  });
+
+  it("fails when the refund was already claimed", async () => {
+    try {
+      await program.methods
+        .claimRefundFromMarket()
+        .accountsPartial({
+          market: marketPda,
+          position: positionPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("NoRefundToClaim"));
+    }
+  });
+});
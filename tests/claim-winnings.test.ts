@@ -10,6 +10,7 @@ import {
   derivePositionPda,
   getTimeValues,
   generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
 } from "./utils";
 import { getAccount } from "@solana/spl-token";
 
@@ -38,6 +39,8 @@ describe("Claim Winnings", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -62,7 +65,7 @@ describe("Claim Winnings", () => {
     );
 
     await program.methods
-      .placeBetOnMarket(betAmount, { home: {} })
+      .placeBetOnMarket(betAmount, { home: {} }, null)
       .accountsPartial({
         market: marketPda,
         user: context.user1.publicKey,
@@ -115,6 +118,8 @@ describe("Claim Winnings", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -131,7 +136,7 @@ describe("Claim Winnings", () => {
     // Place a bet on Away
     const betAmount = new anchor.BN(50_000_000);
     await program.methods
-      .placeBetOnMarket(betAmount, { away: {} })
+      .placeBetOnMarket(betAmount, { away: {} }, null)
       .accountsPartial({
         market: noWinningsMarketPda,
         user: context.user1.publicKey,
@@ -168,6 +173,8 @@ describe("Claim Winnings", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
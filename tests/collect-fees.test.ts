@@ -9,6 +9,8 @@ import {
   deriveVaultPda,
   getTimeValues,
   generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+  PROTOCOL_SHARE_BPS,
 } from "./utils";
 import {
   getAccount,
@@ -42,6 +44,8 @@ describe("Collect Fees", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -61,7 +65,7 @@ describe("Collect Fees", () => {
 
     // User1 bets on Home
     await program.methods
-      .placeBetOnMarket(betAmount1, { home: {} })
+      .placeBetOnMarket(betAmount1, { home: {} }, null)
       .accountsPartial({
         market: marketPda,
         user: context.user1.publicKey,
@@ -74,7 +78,7 @@ describe("Collect Fees", () => {
 
     // User2 bets on Away
     await program.methods
-      .placeBetOnMarket(betAmount2, { away: {} })
+      .placeBetOnMarket(betAmount2, { away: {} }, null)
       .accountsPartial({
         market: marketPda,
         user: context.user2.publicKey,
@@ -119,7 +123,15 @@ describe("Collect Fees", () => {
     // Bet 1: 100 tokens -> fee = 100 * 50 / 10000 = 0.5 tokens = 500_000 (6 decimals)
     // Bet 2: 50 tokens -> fee = 50 * 50 / 10000 = 0.25 tokens = 250_000 (6 decimals)
     // Total fees = 750_000
-    const expectedFees = marketBefore.feesCollected;
+    // The creator keeps what is left after the protocol share
+    const expectedFees = marketBefore.creatorFees;
+    assert.strictEqual(
+      marketBefore.protocolFees.toString(),
+      marketBefore.feesCollected
+        .mul(new anchor.BN(PROTOCOL_SHARE_BPS))
+        .div(new anchor.BN(10_000))
+        .toString()
+    );
 
     assert.ok(expectedFees.gt(new anchor.BN(0)), "Should have collected fees");
 
@@ -152,9 +164,13 @@ describe("Collect Fees", () => {
       BigInt(expectedFees.toString())
     );
 
-    // Verify fees_collected was reset to 0
+    // Only the protocol share is left for the treasury to claim
     const marketAfter = await program.account.market.fetch(marketPda);
-    assert.strictEqual(marketAfter.feesCollected.toString(), "0");
+    assert.strictEqual(marketAfter.creatorFees.toString(), "0");
+    assert.strictEqual(
+      marketAfter.feesCollected.toString(),
+      marketBefore.protocolFees.toString()
+    );
   });
 
   it("fails when not the market authority", async () => {
@@ -191,6 +207,8 @@ describe("Collect Fees", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -205,7 +223,7 @@ describe("Collect Fees", () => {
 
     // Place a bet to generate fees
     await program.methods
-      .placeBetOnMarket(new anchor.BN(100_000_000), { home: {} })
+      .placeBetOnMarket(new anchor.BN(100_000_000), { home: {} }, null)
       .accountsPartial({
         market: newMarketPda,
         user: context.user1.publicKey,
@@ -254,6 +272,8 @@ describe("Collect Fees", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -352,6 +372,8 @@ describe("Collect Fees", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -369,7 +391,7 @@ describe("Collect Fees", () => {
 
     // Place a bet to generate fees
     await program.methods
-      .placeBetOnMarket(new anchor.BN(100_000_000), { home: {} })
+      .placeBetOnMarket(new anchor.BN(100_000_000), { home: {} }, null)
       .accountsPartial({
         market: wrongMintMarketPda,
         user: context.user1.publicKey,
@@ -401,4 +423,82 @@ describe("Collect Fees", () => {
       );
     }
   });
+
+  it("fails when the market has been voided", async () => {
+    const feeReceiver = getAssociatedTokenAddressSync(
+      context.mint,
+      context.authority.publicKey
+    );
+
+    // Create a new market with fees
+    const voidedGameKey = "GAME_FEES_VOIDED";
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    const oracleFeedHash = generateOracleFeedHash();
+
+    await program.methods
+      .createFootballMarket(
+        "Voided fees test",
+        "Team X",
+        "Team Y",
+        voidedGameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const [voidedMarketPda] = deriveMarketPda(program.programId, voidedGameKey);
+
+    // Place a bet to generate fees
+    await program.methods
+      .placeBetOnMarket(new anchor.BN(100_000_000), { home: {} }, null)
+      .accountsPartial({
+        market: voidedMarketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    await program.methods
+      .cancelMarket()
+      .accounts({
+        market: voidedMarketPda,
+        authority: context.authority.publicKey,
+        authorityTokenAccount: feeReceiver,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    // Fees now belong to the bettors and must stay in the vault
+    try {
+      await program.methods
+        .collectFeesFromMarket()
+        .accounts({
+          market: voidedMarketPda,
+          authority: context.authority.publicKey,
+          feeReceiver: feeReceiver,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("MarketVoided"));
+    }
+  });
 });
@@ -0,0 +1,243 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveMarketPda,
+  deriveCreatorMarketPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  HOME_OUTCOME,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccount,
+} from "@solana/spl-token";
+
+describe("Conditional Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let authorityTokenAccount: PublicKey;
+  let parentPda: PublicKey;
+  let childPda: PublicKey;
+  const parentKey = "GAME_CONDITIONAL_PARENT";
+  const childKey = "CONDITIONAL_CHILD_001";
+  const betAmount = new anchor.BN(40_000_000);
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    authorityTokenAccount = getAssociatedTokenAddressSync(
+      context.mint,
+      context.authority.publicKey
+    );
+
+    try {
+      await createAssociatedTokenAccount(
+        context.provider.connection,
+        context.authority,
+        context.mint,
+        context.authority.publicKey
+      );
+    } catch (error) {
+      // Account might already exist, that's okay
+    }
+
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    await program.methods
+      .createFootballMarket(
+        "Conditional parent",
+        "Team A",
+        "Team B",
+        parentKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [parentPda] = deriveMarketPda(program.programId, parentKey);
+  });
+
+  it("creates a child market that requires the parent outcome", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(4);
+
+    await program.methods
+      .createConditionalMarket(
+        "If Team A wins, does the striker score?",
+        childKey,
+        ["Yes", "No"],
+        HOME_OUTCOME,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        parentMarket: parentPda,
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [childPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      childKey
+    );
+    const market = await program.account.market.fetch(childPda);
+    assert.strictEqual(market.parentMarket.toString(), parentPda.toString());
+    assert.strictEqual(market.parentOutcome, HOME_OUTCOME);
+    assert.deepStrictEqual(market.outcomeLabels, ["Yes", "No"]);
+  });
+
+  it("rejects a parent outcome the parent does not have", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(4);
+
+    try {
+      await program.methods
+        .createConditionalMarket(
+          "Invalid condition",
+          "CONDITIONAL_CHILD_BAD",
+          ["Yes", "No"],
+          7,
+          startTime,
+          endTime,
+          resolutionTime,
+          generateOracleFeedHash(),
+          DEFAULT_FEE_BPS,
+        )
+        .accountsPartial({
+          parentMarket: parentPda,
+          authority: context.authority.publicKey,
+          mint: context.mint,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidOutcome"));
+    }
+  });
+
+  it("takes bets while the parent is still open", async () => {
+    await program.methods
+      .placeBetOnOutcome(betAmount, 0, null)
+      .accountsPartial({
+        market: childPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const market = await program.account.market.fetch(childPda);
+    assert.strictEqual(market.pools[0].toString(), betAmount.toString());
+  });
+
+  it("keeps fees in the vault until the child resolves", async () => {
+    try {
+      await program.methods
+        .collectFeesFromMarket()
+        .accounts({
+          market: childPda,
+          authority: context.authority.publicKey,
+          feeReceiver: authorityTokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("MarketNotResolved"));
+    }
+  });
+
+  it("cannot be voided before the parent settles", async () => {
+    try {
+      await program.methods
+        .voidConditionalMarket()
+        .accounts({
+          market: childPda,
+          parentMarket: parentPda,
+        } as any)
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("ParentNotResolved"));
+    }
+  });
+
+  it("is voided by anyone once the parent misses the condition, and refunds bettors", async () => {
+    await program.methods
+      .cancelMarket()
+      .accounts({
+        market: parentPda,
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    await program.methods
+      .voidConditionalMarket()
+      .accounts({
+        market: childPda,
+        parentMarket: parentPda,
+      } as any)
+      .rpc();
+
+    const market = await program.account.market.fetch(childPda);
+    assert.strictEqual(market.voided, true);
+
+    const before = await getAccount(context.provider.connection, context.user1TokenAccount);
+    const [positionPda] = derivePositionPda(program.programId, childPda, context.user1.publicKey);
+
+    await program.methods
+      .claimRefundFromMarket()
+      .accountsPartial({
+        market: childPda,
+        position: positionPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const after = await getAccount(context.provider.connection, context.user1TokenAccount);
+    const expectedFee = betAmount.mul(new anchor.BN(50)).div(new anchor.BN(10_000));
+    assert.strictEqual(
+      (after.amount - before.amount).toString(),
+      betAmount.add(expectedFee).toString()
+    );
+  });
+});
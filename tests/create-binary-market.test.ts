@@ -0,0 +1,126 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  deriveVaultPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+
+describe("Create Binary Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  const marketKey = "ELECTION_2026";
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+  });
+
+  it("creates a market with arbitrary outcome labels and a category", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+
+    await program.methods
+      .createBinaryMarket(
+        "Who wins the election?",
+        marketKey,
+        "Candidate X",
+        "Candidate Y",
+        "elections",
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+    const [vaultPda] = deriveVaultPda(program.programId, marketPda);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.question, "Who wins the election?");
+    assert.deepStrictEqual(market.outcomeLabels, ["Candidate X", "Candidate Y"]);
+    assert.strictEqual(market.category, "elections");
+    assert.strictEqual(market.homeTeam, "");
+    assert.strictEqual(market.awayTeam, "");
+    assert.strictEqual(market.vault.toString(), vaultPda.toString());
+  });
+
+  it("lets another creator reuse the same market key", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+
+    await program.methods
+      .createBinaryMarket(
+        "Who wins the election?",
+        marketKey,
+        "Yes",
+        "No",
+        null,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.user1.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const [otherMarketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.user1.publicKey,
+      marketKey
+    );
+    const market = await program.account.market.fetch(otherMarketPda);
+    assert.strictEqual(market.category, "");
+    assert.strictEqual(market.authority.toString(), context.user1.publicKey.toString());
+  });
+
+  it("takes bets through the shared position and vault flow", async () => {
+    const betAmount = new anchor.BN(25_000_000);
+
+    await program.methods
+      .placeBetOnOutcome(betAmount, 1, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user2.publicKey,
+        userTokenAccount: context.user2TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user2])
+      .rpc();
+
+    const [positionPda] = derivePositionPda(
+      program.programId,
+      marketPda,
+      context.user2.publicKey
+    );
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.amounts[1].toString(), betAmount.toString());
+  });
+});
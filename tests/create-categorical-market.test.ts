@@ -0,0 +1,168 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  deriveVaultPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+
+describe("Create Categorical Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  const marketKey = "CUP_WINNER_001";
+  const outcomeLabels = ["Team A", "Team B", "Team C", "Team D", "Team E"];
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+  });
+
+  it("creates a market with labelled outcomes", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    const oracleFeedHash = generateOracleFeedHash();
+
+    await program.methods
+      .createCategoricalMarket(
+        "Who will win the cup?",
+        marketKey,
+        outcomeLabels,
+        startTime,
+        endTime,
+        resolutionTime,
+        oracleFeedHash,
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+    const [vaultPda] = deriveVaultPda(program.programId, marketPda);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.question, "Who will win the cup?");
+    assert.strictEqual(market.gameKey, marketKey);
+    assert.deepStrictEqual(market.marketType, { categorical: {} });
+    assert.deepStrictEqual(market.outcomeLabels, outcomeLabels);
+    assert.strictEqual(market.pools.length, outcomeLabels.length);
+    assert.ok(market.pools.every((pool) => pool.toString() === "0"));
+    assert.strictEqual(market.resolved, false);
+    assert.strictEqual(market.vault.toString(), vaultPda.toString());
+  });
+
+  it("places a bet on an outcome index", async () => {
+    const betAmount = new anchor.BN(40_000_000); // 40 tokens
+    const [positionPda] = derivePositionPda(
+      program.programId,
+      marketPda,
+      context.user1.publicKey
+    );
+
+    await program.methods
+      .placeBetOnOutcome(betAmount, 3, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.pools[3].toString(), betAmount.toString());
+
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.amounts.length, outcomeLabels.length);
+    assert.strictEqual(position.amounts[3].toString(), betAmount.toString());
+  });
+
+  it("fails when the outcome index is out of range", async () => {
+    try {
+      await program.methods
+        .placeBetOnOutcome(new anchor.BN(10_000_000), outcomeLabels.length, null)
+        .accountsPartial({
+          market: marketPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidOutcome"));
+    }
+  });
+
+  it("rejects home/away/draw bets on a categorical market", async () => {
+    try {
+      await program.methods
+        .placeBetOnMarket(new anchor.BN(10_000_000), { home: {} }, null)
+        .accountsPartial({
+          market: marketPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidOutcome"));
+    }
+  });
+
+  it("fails with fewer than two outcomes", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    const oracleFeedHash = generateOracleFeedHash();
+
+    try {
+      await program.methods
+        .createCategoricalMarket(
+          "Single outcome market",
+          "SINGLE_OUTCOME",
+          ["Only option"],
+          startTime,
+          endTime,
+          resolutionTime,
+          oracleFeedHash,
+          DEFAULT_FEE_BPS,
+        )
+        .accountsPartial({
+          authority: context.authority.publicKey,
+          mint: context.mint,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidOutcomeCount"));
+    }
+  });
+});
@@ -7,8 +7,21 @@ import {
   setupTestContext,
   deriveMarketPda,
   deriveVaultPda,
+  deriveTotalGoalsMarketPda,
+  deriveAsianHandicapMarketPda,
+  deriveCorrectScoreMarketPda,
+  derivePositionPda,
+  correctScoreOutcome,
   getTimeValues,
   generateOracleFeedHash,
+  HOME_OUTCOME,
+  AWAY_OUTCOME,
+  DRAW_OUTCOME,
+  OVER_OUTCOME,
+  UNDER_OUTCOME,
+  HANDICAP_HOME_OUTCOME,
+  HANDICAP_AWAY_OUTCOME,
+  DEFAULT_FEE_BPS,
 } from "./utils";
 
 describe("Create Market", () => {
@@ -35,6 +48,8 @@ describe("Create Market", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -58,10 +73,288 @@ describe("Create Market", () => {
     assert.strictEqual(market.homeTeam, "Team A");
     assert.strictEqual(market.awayTeam, "Team B");
     assert.strictEqual(market.gameKey, gameKey);
-    assert.strictEqual(market.yesPool.toString(), "0");
-    assert.strictEqual(market.noPool.toString(), "0");
-    assert.strictEqual(market.drawPool.toString(), "0");
+    assert.strictEqual(market.pools[HOME_OUTCOME].toString(), "0");
+    assert.strictEqual(market.pools[AWAY_OUTCOME].toString(), "0");
+    assert.strictEqual(market.pools[DRAW_OUTCOME].toString(), "0");
+    assert.deepStrictEqual(market.marketType, { matchResult: {} });
+    assert.strictEqual(market.outcomeLabels[HOME_OUTCOME], "Team A");
+    assert.strictEqual(market.outcomeLabels[AWAY_OUTCOME], "Team B");
+    assert.strictEqual(market.outcomeLabels[DRAW_OUTCOME], "Draw");
     assert.strictEqual(market.resolved, false);
     assert.strictEqual(market.vault.toString(), vaultPda.toString());
   });
+
+  it("creates an over/under market alongside the match result market", async () => {
+    const gameKey = "GAME_TOTALS_001";
+    const line = 250; // 2.5 goals
+    const { startTime, endTime, resolutionTime } = getTimeValues();
+
+    await program.methods
+      .createFootballMarket(
+        "Will home team win?",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    await program.methods
+      .createFootballMarket(
+        "Over/Under 2.5 goals",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { totalGoals: { line } },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        market: deriveTotalGoalsMarketPda(program.programId, gameKey, line)[0],
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const [matchResultPda] = deriveMarketPda(program.programId, gameKey);
+    const [totalsPda] = deriveTotalGoalsMarketPda(program.programId, gameKey, line);
+    assert.notStrictEqual(matchResultPda.toString(), totalsPda.toString());
+
+    const market = await program.account.market.fetch(totalsPda);
+    assert.strictEqual(market.gameKey, gameKey);
+    assert.deepStrictEqual(market.marketType, { totalGoals: { line } });
+    assert.strictEqual(market.outcomeLabels[OVER_OUTCOME], "Over 2.5");
+    assert.strictEqual(market.outcomeLabels[UNDER_OUTCOME], "Under 2.5");
+    assert.strictEqual(market.pools.length, 2);
+  });
+
+  it("fails with a quarter goal line", async () => {
+    const gameKey = "GAME_TOTALS_QUARTER";
+    const line = 225; // 2.25 goals
+    const { startTime, endTime, resolutionTime } = getTimeValues();
+
+    try {
+      await program.methods
+        .createFootballMarket(
+          "Over/Under 2.25 goals",
+          "Team A",
+          "Team B",
+          gameKey,
+          startTime,
+          endTime,
+          resolutionTime,
+          generateOracleFeedHash(),
+          { totalGoals: { line } },
+          DEFAULT_FEE_BPS,
+        )
+        .accountsPartial({
+          authority: context.authority.publicKey,
+          market: deriveTotalGoalsMarketPda(program.programId, gameKey, line)[0],
+          mint: context.mint,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidLine"));
+    }
+  });
+
+  it("fails with a fee above the protocol maximum", async () => {
+    const gameKey = "GAME_FEE_TOO_HIGH";
+    const { startTime, endTime, resolutionTime } = getTimeValues();
+
+    try {
+      await program.methods
+        .createFootballMarket(
+          "Will home team win?",
+          "Team A",
+          "Team B",
+          gameKey,
+          startTime,
+          endTime,
+          resolutionTime,
+          generateOracleFeedHash(),
+          { matchResult: {} },
+          501,
+        )
+        .accountsPartial({
+          authority: context.authority.publicKey,
+          mint: context.mint,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("FeeTooHigh"));
+    }
+  });
+
+  it("creates quarter line asian handicap markets for one fixture", async () => {
+    const gameKey = "GAME_HANDICAP_001";
+    const { startTime, endTime, resolutionTime } = getTimeValues();
+
+    for (const line of [-150, -25]) {
+      await program.methods
+        .createFootballMarket(
+          "Asian handicap",
+          "Team A",
+          "Team B",
+          gameKey,
+          startTime,
+          endTime,
+          resolutionTime,
+          generateOracleFeedHash(),
+          { asianHandicap: { line } },
+          DEFAULT_FEE_BPS,
+        )
+        .accountsPartial({
+          authority: context.authority.publicKey,
+          market: deriveAsianHandicapMarketPda(program.programId, gameKey, line)[0],
+          mint: context.mint,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+    }
+
+    const [halfLinePda] = deriveAsianHandicapMarketPda(program.programId, gameKey, -150);
+    const halfLine = await program.account.market.fetch(halfLinePda);
+    assert.strictEqual(halfLine.outcomeLabels[HANDICAP_HOME_OUTCOME], "Team A -1.5");
+    assert.strictEqual(halfLine.outcomeLabels[HANDICAP_AWAY_OUTCOME], "Team B +1.5");
+
+    const [quarterLinePda] = deriveAsianHandicapMarketPda(program.programId, gameKey, -25);
+    const quarterLine = await program.account.market.fetch(quarterLinePda);
+    assert.deepStrictEqual(quarterLine.marketType, { asianHandicap: { line: -25 } });
+    assert.strictEqual(quarterLine.outcomeLabels[HANDICAP_HOME_OUTCOME], "Team A -0.25");
+    assert.strictEqual(quarterLine.outcomeLabels[HANDICAP_AWAY_OUTCOME], "Team B +0.25");
+  });
+
+  it("fails with a handicap line that is not a quarter goal", async () => {
+    const gameKey = "GAME_HANDICAP_INVALID";
+    const line = -110;
+    const { startTime, endTime, resolutionTime } = getTimeValues();
+
+    try {
+      await program.methods
+        .createFootballMarket(
+          "Asian handicap",
+          "Team A",
+          "Team B",
+          gameKey,
+          startTime,
+          endTime,
+          resolutionTime,
+          generateOracleFeedHash(),
+          { asianHandicap: { line } },
+          DEFAULT_FEE_BPS,
+        )
+        .accountsPartial({
+          authority: context.authority.publicKey,
+          market: deriveAsianHandicapMarketPda(program.programId, gameKey, line)[0],
+          mint: context.mint,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidLine"));
+    }
+  });
+
+  it("creates a correct score market with a bucket per score", async () => {
+    const gameKey = "GAME_CORRECT_SCORE_001";
+    const maxGoals = 5;
+    const { startTime, endTime, resolutionTime } = getTimeValues();
+    const [marketPda] = deriveCorrectScoreMarketPda(program.programId, gameKey, maxGoals);
+
+    await program.methods
+      .createFootballMarket(
+        "Correct score",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { correctScore: { maxGoals } },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        market: marketPda,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.outcomeLabels.length, 37);
+    assert.strictEqual(market.pools.length, 37);
+    assert.strictEqual(market.outcomeLabels[0], "0-0");
+    assert.strictEqual(market.outcomeLabels[correctScoreOutcome(maxGoals, 2, 1)], "2-1");
+    assert.strictEqual(market.outcomeLabels[correctScoreOutcome(maxGoals, 5, 5)], "5-5");
+    assert.strictEqual(market.outcomeLabels[correctScoreOutcome(maxGoals, 7, 0)], "Any other");
+
+    // Bets go through the same vault and fee flow as every other market
+    const betAmount = new anchor.BN(20_000_000);
+    const scoreIndex = correctScoreOutcome(maxGoals, 2, 1);
+    await program.methods
+      .placeBetOnOutcome(betAmount, scoreIndex, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const [positionPda] = derivePositionPda(
+      program.programId,
+      marketPda,
+      context.user1.publicKey
+    );
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.amounts[scoreIndex].toString(), betAmount.toString());
+
+    const updated = await program.account.market.fetch(marketPda);
+    assert.strictEqual(updated.pools[scoreIndex].toString(), betAmount.toString());
+    assert.strictEqual(
+      updated.feesCollected.toString(),
+      betAmount.mul(new anchor.BN(50)).div(new anchor.BN(10_000)).toString()
+    );
+  });
 });
@@ -0,0 +1,138 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  LONG_OUTCOME,
+  SHORT_OUTCOME,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+
+describe("Create Scalar Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  const marketKey = "CORNERS_001";
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+  });
+
+  it("creates a long/short market between bounds", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+
+    await program.methods
+      .createScalarMarket(
+        "Total corners",
+        marketKey,
+        new anchor.BN(0),
+        new anchor.BN(20),
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+
+    const market = await program.account.market.fetch(marketPda);
+    const marketType = market.marketType as any;
+    assert.strictEqual(marketType.scalar.lower.toString(), "0");
+    assert.strictEqual(marketType.scalar.upper.toString(), "20");
+    assert.strictEqual(market.outcomeLabels[LONG_OUTCOME], "Long 0-20");
+    assert.strictEqual(market.outcomeLabels[SHORT_OUTCOME], "Short 0-20");
+    assert.strictEqual(market.finalResultValue.toString(), "0");
+  });
+
+  it("takes long and short bets", async () => {
+    const longAmount = new anchor.BN(30_000_000);
+    const shortAmount = new anchor.BN(10_000_000);
+
+    await program.methods
+      .placeBetOnOutcome(longAmount, LONG_OUTCOME, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    await program.methods
+      .placeBetOnOutcome(shortAmount, SHORT_OUTCOME, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user2.publicKey,
+        userTokenAccount: context.user2TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user2])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.pools[LONG_OUTCOME].toString(), longAmount.toString());
+    assert.strictEqual(market.pools[SHORT_OUTCOME].toString(), shortAmount.toString());
+
+    const [positionPda] = derivePositionPda(
+      program.programId,
+      marketPda,
+      context.user2.publicKey
+    );
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.amounts[SHORT_OUTCOME].toString(), shortAmount.toString());
+  });
+
+  it("fails when the upper bound is not above the lower bound", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+
+    try {
+      await program.methods
+        .createScalarMarket(
+          "Possession %",
+          "POSSESSION_INVALID",
+          new anchor.BN(60),
+          new anchor.BN(40),
+          startTime,
+          endTime,
+          resolutionTime,
+          generateOracleFeedHash(),
+          DEFAULT_FEE_BPS,
+        )
+        .accountsPartial({
+          authority: context.authority.publicKey,
+          mint: context.mint,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidScalarBounds"));
+    }
+  });
+});
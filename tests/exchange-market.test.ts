@@ -0,0 +1,252 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  deriveOrderBookPda,
+  derivePositionPda,
+  deriveUserVolumePda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+import { getAccount } from "@solana/spl-token";
+
+describe("Exchange Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  let orderBookPda: PublicKey;
+  const marketKey = "EXCHANGE_MARKET_001";
+
+  async function placeBackOrder(outcome: number, odds: number, stake: number) {
+    await program.methods
+      .placeOrder({ back: {} }, outcome, new anchor.BN(odds), new anchor.BN(stake))
+      .accountsPartial({
+        market: marketPda,
+        orderBook: orderBookPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+  });
+
+  it("creates a market with an empty order book", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+
+    await program.methods
+      .createExchangeMarket(
+        "Who wins the derby?",
+        marketKey,
+        ["Home", "Away", "Draw"],
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+    [orderBookPda] = deriveOrderBookPda(program.programId, marketPda);
+
+    const orderBook = await program.account.orderBook.fetch(orderBookPda);
+    assert.strictEqual(orderBook.market.toString(), marketPda.toString());
+    assert.strictEqual(orderBook.orders.length, 0);
+  });
+
+  it("rests an unmatched back order with its stake and fee in escrow", async () => {
+    const stake = 10_000_000;
+    const before = await getAccount(context.provider.connection, context.user1TokenAccount);
+
+    await program.methods
+      .placeOrder({ back: {} }, 0, new anchor.BN(25_000), new anchor.BN(stake))
+      .accountsPartial({
+        market: marketPda,
+        orderBook: orderBookPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const after = await getAccount(context.provider.connection, context.user1TokenAccount);
+    const fee = Math.floor(stake * 50 / 10_000);
+    assert.strictEqual(Number(before.amount - after.amount), stake + fee);
+
+    const orderBook = await program.account.orderBook.fetch(orderBookPda);
+    assert.strictEqual(orderBook.orders.length, 1);
+    assert.strictEqual(orderBook.orders[0].escrow.toNumber(), stake);
+    assert.strictEqual(orderBook.orders[0].feeReserved.toNumber(), fee);
+  });
+
+  it("matches a crossing lay order at the resting odds", async () => {
+    const [makerPositionPda] = derivePositionPda(program.programId, marketPda, context.user1.publicKey);
+    const [makerVolumePda] = deriveUserVolumePda(program.programId, context.user1.publicKey);
+    const [takerPositionPda] = derivePositionPda(program.programId, marketPda, context.user2.publicKey);
+
+    await program.methods
+      .createUserVolume()
+      .accountsPartial({
+        user: context.user1.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    // Lays 4 tokens of backer stake at up to 3.0, fills at the resting 2.5
+    await program.methods
+      .placeOrder({ lay: {} }, 0, new anchor.BN(30_000), new anchor.BN(4_000_000))
+      .accountsPartial({
+        market: marketPda,
+        orderBook: orderBookPda,
+        user: context.user2.publicKey,
+        userTokenAccount: context.user2TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts([
+        { pubkey: makerPositionPda, isWritable: true, isSigner: false },
+        { pubkey: makerVolumePda, isWritable: true, isSigner: false },
+      ])
+      .signers([context.user2])
+      .rpc();
+
+    const backer = await program.account.position.fetch(makerPositionPda);
+    assert.deepStrictEqual(backer.amounts.slice(0, 3).map((a) => a.toNumber()), [10_000_000, 0, 0]);
+    assert.strictEqual(backer.exchangePaid.toNumber(), 4_000_000);
+    // The maker's matched stake counts towards their volume
+    assert.strictEqual(backer.volume.toNumber(), 4_000_000);
+    const makerVolume = await program.account.userVolume.fetch(makerVolumePda);
+    assert.strictEqual(makerVolume.cumulativeStake.toNumber(), 4_000_000);
+
+    const layer = await program.account.position.fetch(takerPositionPda);
+    assert.deepStrictEqual(layer.amounts.slice(0, 3).map((a) => a.toNumber()), [0, 10_000_000, 10_000_000]);
+    assert.strictEqual(layer.exchangePaid.toNumber(), 6_000_000);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.collateral.toNumber(), 10_000_000);
+
+    const orderBook = await program.account.orderBook.fetch(orderBookPda);
+    assert.strictEqual(orderBook.orders.length, 1);
+    assert.strictEqual(orderBook.orders[0].stake.toNumber(), 6_000_000);
+  });
+
+  it("only lets the owner cancel before the start", async () => {
+    const orderBook = await program.account.orderBook.fetch(orderBookPda);
+    const order = orderBook.orders[0];
+
+    try {
+      await program.methods
+        .cancelOrder(order.orderId)
+        .accountsPartial({
+          market: marketPda,
+          orderBook: orderBookPda,
+          caller: context.user2.publicKey,
+          ownerTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user2])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedOrderCancel"));
+    }
+
+    const before = await getAccount(context.provider.connection, context.user1TokenAccount);
+
+    await program.methods
+      .cancelOrder(order.orderId)
+      .accountsPartial({
+        market: marketPda,
+        orderBook: orderBookPda,
+        caller: context.user1.publicKey,
+        ownerTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const after = await getAccount(context.provider.connection, context.user1TokenAccount);
+    assert.strictEqual(
+      Number(after.amount - before.amount),
+      order.escrow.toNumber() + order.feeReserved.toNumber()
+    );
+
+    const cleared = await program.account.orderBook.fetch(orderBookPda);
+    assert.strictEqual(cleared.orders.length, 0);
+  });
+
+  it("fails with a stake below the minimum order size", async () => {
+    try {
+      await placeBackOrder(1, 25_000, 999_999);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("OrderTooSmall"));
+    }
+  });
+
+  it("caps the resting orders one owner can have on the book", async () => {
+    for (let i = 0; i < 4; i++) {
+      await placeBackOrder(1, 20_000 + i * 1_000, 1_000_000);
+    }
+
+    try {
+      await placeBackOrder(1, 25_000, 1_000_000);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("TooManyOrders"));
+    }
+
+    const orderBook = await program.account.orderBook.fetch(orderBookPda);
+    assert.strictEqual(orderBook.orders.length, 4);
+  });
+
+  it("fails to rest a crossing order when the makers are not passed", async () => {
+    try {
+      await program.methods
+        .placeOrder({ lay: {} }, 1, new anchor.BN(30_000), new anchor.BN(1_000_000))
+        .accountsPartial({
+          market: marketPda,
+          orderBook: orderBookPda,
+          user: context.user2.publicKey,
+          userTokenAccount: context.user2TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user2])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("OrderWouldCross"));
+    }
+  });
+});
@@ -0,0 +1,160 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveMarketPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+  HOME_OUTCOME,
+} from "./utils";
+
+// Matches DEFAULT_FALLBACK_GRACE_PERIOD in the program
+const DEFAULT_GRACE_PERIOD = 7 * 86_400;
+
+describe("Fallback Resolution", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+
+  async function createMarket(gameKey: string, offsetHours: number): Promise<PublicKey> {
+    const { startTime, endTime, resolutionTime } = getTimeValues(offsetHours);
+    await program.methods
+      .createFootballMarket(
+        "Will home team win?",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    return deriveMarketPda(program.programId, gameKey)[0];
+  }
+
+  async function setGracePeriod(market: PublicKey, seconds: number) {
+    await program.methods
+      .updateFallbackGracePeriod(new anchor.BN(seconds))
+      .accounts({
+        market,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+  });
+
+  it("gives new markets the default grace period", async () => {
+    const marketPda = await createMarket("GAME_FALLBACK_DEFAULT", 2);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.fallbackGracePeriod.toNumber(), DEFAULT_GRACE_PERIOD);
+    assert.strictEqual(market.fallback, null);
+  });
+
+  it("shortens the grace period before the market starts", async () => {
+    const marketPda = await createMarket("GAME_FALLBACK_UPCOMING", 2);
+    await setGracePeriod(marketPda, 86_400);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.fallbackGracePeriod.toNumber(), 86_400);
+  });
+
+  describe("once the oracle is overdue", () => {
+    let marketPda: PublicKey;
+
+    before(async () => {
+      // Kicked off four hours ago, resolvable for the last hour
+      marketPda = await createMarket("GAME_FALLBACK_OVERDUE", -4);
+    });
+
+    it("fails to shorten the grace period after the start", async () => {
+      try {
+        await setGracePeriod(marketPda, 0);
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        assert.ok(error.message.includes("MarketAlreadyStarted"));
+      }
+    });
+
+    it("fails to announce a fallback during the grace period", async () => {
+      try {
+        await program.methods
+          .announceFallbackResolution(new anchor.BN(HOME_OUTCOME))
+          .accountsPartial({
+            market: marketPda,
+            authority: context.authority.publicKey,
+            parentMarket: null,
+          } as any)
+          .signers([context.authority])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        assert.ok(error.message.includes("GracePeriodActive"));
+      }
+    });
+
+    it("fails to announce a fallback when not the market authority", async () => {
+      try {
+        await program.methods
+          .announceFallbackResolution(new anchor.BN(HOME_OUTCOME))
+          .accountsPartial({
+            market: marketPda,
+            authority: context.user1.publicKey,
+            parentMarket: null,
+          } as any)
+          .signers([context.user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        assert.ok(error.message.includes("UnauthorizedFallback"));
+      }
+    });
+
+    it("fails to execute a fallback that was never announced", async () => {
+      try {
+        await program.methods
+          .executeFallbackResolution()
+          .accountsPartial({
+            market: marketPda,
+            authority: context.authority.publicKey,
+            parentMarket: null,
+          } as any)
+          .signers([context.authority])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        assert.ok(error.message.includes("FallbackNotAnnounced"));
+      }
+    });
+
+    it("extends the grace period after the start", async () => {
+      await setGracePeriod(marketPda, DEFAULT_GRACE_PERIOD * 2);
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.strictEqual(market.fallbackGracePeriod.toNumber(), DEFAULT_GRACE_PERIOD * 2);
+    });
+  });
+});
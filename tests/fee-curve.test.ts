@@ -0,0 +1,136 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  getTimeValues,
+  generateOracleFeedHash,
+} from "./utils";
+
+describe("Fee Curve", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  const marketKey = "FEE_CURVE_MARKET_001";
+
+  async function placeBet(user: Keypair, userTokenAccount: PublicKey, amount: number, outcome: number) {
+    const before = await program.account.market.fetch(marketPda);
+    await program.methods
+      .placeBetOnOutcome(new anchor.BN(amount), outcome, null)
+      .accountsPartial({
+        market: marketPda,
+        user: user.publicKey,
+        userTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([user])
+      .rpc();
+    const after = await program.account.market.fetch(marketPda);
+
+    return after.feesCollected.sub(before.feesCollected).toString();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    await program.methods
+      .createCategoricalMarket(
+        "Who will win the final?",
+        marketKey,
+        ["Team A", "Team B"],
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        100,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+  });
+
+  it("fails with a curve that does not span the market fee", async () => {
+    try {
+      await program.methods
+        .updateFeeCurve({ minFeeBps: 150, maxFeeBps: 300 })
+        .accounts({
+          market: marketPda,
+          authority: context.authority.publicKey,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidFeeCurve"));
+    }
+  });
+
+  it("fails when not the market authority", async () => {
+    try {
+      await program.methods
+        .updateFeeCurve({ minFeeBps: 20, maxFeeBps: 300 })
+        .accounts({
+          market: marketPda,
+          authority: context.user1.publicKey,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedFeeUpdate"));
+    }
+  });
+
+  it("sets a fee curve around the market fee", async () => {
+    await program.methods
+      .updateFeeCurve({ minFeeBps: 20, maxFeeBps: 300 })
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.deepStrictEqual(market.feeCurve, { minFeeBps: 20, maxFeeBps: 300 });
+  });
+
+  it("charges the most for a bet that takes the whole pot", async () => {
+    const fee = await placeBet(context.user1, context.user1TokenAccount, 100_000_000, 0);
+    assert.strictEqual(fee, "3000000"); // 300 bps
+  });
+
+  it("charges the market fee for a bet that evens the pools", async () => {
+    const fee = await placeBet(context.user2, context.user2TokenAccount, 100_000_000, 1);
+    assert.strictEqual(fee, "1000000"); // 100 bps
+  });
+
+  it("scales the fee with the imbalance a bet leaves behind", async () => {
+    // Team B ends up with 2/3 of the pot: 100 + 200 * 1666 / 5000 bps
+    const surcharged = await placeBet(context.user2, context.user2TokenAccount, 100_000_000, 1);
+    assert.strictEqual(surcharged, "1660000");
+
+    // Team A ends up with 35.48% of the pot: 100 - 80 * 1452 / 5000 bps
+    const discounted = await placeBet(context.user1, context.user1TokenAccount, 10_000_000, 0);
+    assert.strictEqual(discounted, "77000");
+  });
+});
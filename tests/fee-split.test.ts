@@ -0,0 +1,280 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  deriveProtocolConfigPda,
+  deriveReferralPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+  PROTOCOL_SHARE_BPS,
+  REFERRER_SHARE_BPS,
+} from "./utils";
+import {
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccount,
+} from "@solana/spl-token";
+
+describe("Fee Split", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  let referralPda: PublicKey;
+  let authorityTokenAccount: PublicKey;
+  const marketKey = "FEE_SPLIT_MARKET_001";
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    authorityTokenAccount = getAssociatedTokenAddressSync(
+      context.mint,
+      context.authority.publicKey
+    );
+    try {
+      await createAssociatedTokenAccount(
+        context.provider.connection,
+        context.authority,
+        context.mint,
+        context.authority.publicKey
+      );
+    } catch (error) {
+      // Account might already exist, that's okay
+    }
+
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    await program.methods
+      .createCategoricalMarket(
+        "Who will win the league?",
+        marketKey,
+        ["Team A", "Team B"],
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+    [referralPda] = deriveReferralPda(
+      program.programId,
+      marketPda,
+      context.user2.publicKey
+    );
+  });
+
+  it("has the protocol config set up", async () => {
+    const [configPda] = deriveProtocolConfigPda(program.programId);
+    const config = await program.account.protocolConfig.fetch(configPda);
+    assert.ok(config.admin.equals(context.authority.publicKey));
+    assert.ok(config.treasury.equals(context.authority.publicKey));
+    assert.strictEqual(config.protocolShareBps, PROTOCOL_SHARE_BPS);
+    assert.strictEqual(config.referrerShareBps, REFERRER_SHARE_BPS);
+  });
+
+  it("fails to update the config when not the admin", async () => {
+    try {
+      await program.methods
+        .updateProtocolConfig(context.user1.publicKey, 0, 0)
+        .accountsPartial({
+          admin: context.user1.publicKey,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedProtocolAdmin"));
+    }
+  });
+
+  it("fails to update the config with shares above the whole fee", async () => {
+    try {
+      await program.methods
+        .updateProtocolConfig(context.authority.publicKey, 8_000, 3_000)
+        .accountsPartial({
+          admin: context.authority.publicKey,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidFeeSplit"));
+    }
+  });
+
+  it("opens a referral for a referrer", async () => {
+    await program.methods
+      .createReferral()
+      .accountsPartial({
+        market: marketPda,
+        referrer: context.user2.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user2])
+      .rpc();
+
+    const referral = await program.account.referral.fetch(referralPda);
+    assert.ok(referral.market.equals(marketPda));
+    assert.ok(referral.referrer.equals(context.user2.publicKey));
+    assert.strictEqual(referral.feesOwed.toString(), "0");
+  });
+
+  it("splits the fee of a referred bet three ways", async () => {
+    const betAmount = new anchor.BN(100_000_000);
+    await program.methods
+      .placeBetOnOutcome(betAmount, 0, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        referral: referralPda,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    // 0.5 tokens of fee: 20% protocol, 10% referrer, the rest to the creator
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.feesCollected.toString(), "500000");
+    assert.strictEqual(market.protocolFees.toString(), "100000");
+    assert.strictEqual(market.referrerFees.toString(), "50000");
+    assert.strictEqual(market.creatorFees.toString(), "350000");
+
+    const referral = await program.account.referral.fetch(referralPda);
+    assert.strictEqual(referral.feesOwed.toString(), "50000");
+  });
+
+  it("gives the creator the referrer's share of an unreferred bet", async () => {
+    await program.methods
+      .placeBetOnOutcome(new anchor.BN(100_000_000), 1, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.protocolFees.toString(), "200000");
+    assert.strictEqual(market.referrerFees.toString(), "50000");
+    assert.strictEqual(market.creatorFees.toString(), "750000");
+  });
+
+  it("fails when a bettor refers themselves", async () => {
+    try {
+      await program.methods
+        .placeBetOnOutcome(new anchor.BN(10_000_000), 0, null)
+        .accountsPartial({
+          market: marketPda,
+          user: context.user2.publicKey,
+          userTokenAccount: context.user2TokenAccount,
+          referral: referralPda,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user2])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("SelfReferral"));
+    }
+  });
+
+  it("keeps the protocol share in the vault until the market resolves", async () => {
+    try {
+      await program.methods
+        .claimProtocolFees()
+        .accountsPartial({
+          market: marketPda,
+          treasury: context.authority.publicKey,
+          feeReceiver: authorityTokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("MarketNotResolved"));
+    }
+  });
+
+  it("fails to claim protocol fees when not the treasury", async () => {
+    try {
+      await program.methods
+        .claimProtocolFees()
+        .accountsPartial({
+          market: marketPda,
+          treasury: context.user1.publicKey,
+          feeReceiver: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedFeeCollector"));
+    }
+  });
+
+  it("keeps the referral share in the vault until the market resolves", async () => {
+    try {
+      await program.methods
+        .claimReferralFees()
+        .accountsPartial({
+          market: marketPda,
+          referrer: context.user2.publicKey,
+          feeReceiver: context.user2TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user2])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("MarketNotResolved"));
+    }
+  });
+
+  it("lets the creator collect only their share", async () => {
+    await program.methods
+      .collectFeesFromMarket()
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+        feeReceiver: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.creatorFees.toString(), "0");
+    assert.strictEqual(market.feesWithdrawn.toString(), "750000");
+    assert.strictEqual(market.feesCollected.toString(), "250000");
+  });
+});
@@ -0,0 +1,163 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveMarketPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+
+describe("Feed Checks", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  const gameKey = "GAME_FEED_CHECKS";
+
+  async function setFeedChecks(feedChecks: any) {
+    await program.methods
+      .updateFeedChecks(feedChecks)
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    await program.methods
+      .createFootballMarket(
+        "Will home team win?",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveMarketPda(program.programId, gameKey);
+  });
+
+  it("creates markets without feed checks", async () => {
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.feedChecks, null);
+  });
+
+  it("fails with no staleness limit", async () => {
+    try {
+      await setFeedChecks({
+        maxStalenessSlots: new anchor.BN(0),
+        minSamples: 3,
+        maxStdDev: new anchor.BN(0),
+      });
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidFeedChecks"));
+    }
+  });
+
+  it("fails when not the market authority", async () => {
+    try {
+      await program.methods
+        .updateFeedChecks({
+          maxStalenessSlots: new anchor.BN(150),
+          minSamples: 3,
+          maxStdDev: new anchor.BN(0),
+        })
+        .accounts({
+          market: marketPda,
+          authority: context.user1.publicKey,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedUpdater"));
+    }
+  });
+
+  it("stores staleness, sample and deviation limits", async () => {
+    await setFeedChecks({
+      maxStalenessSlots: new anchor.BN(150),
+      minSamples: 3,
+      maxStdDev: new anchor.BN(0),
+    });
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.feedChecks.maxStalenessSlots.toString(), "150");
+    assert.strictEqual(market.feedChecks.minSamples, 3);
+    assert.strictEqual(market.feedChecks.maxStdDev.toString(), "0");
+  });
+
+  it("turns the checks off again", async () => {
+    await setFeedChecks(null);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.feedChecks, null);
+  });
+
+  it("fails to change the checks once the market has started", async () => {
+    // Kicked off four hours ago
+    const startedKey = "GAME_FEED_CHECKS_STARTED";
+    const { startTime, endTime, resolutionTime } = getTimeValues(-4);
+    await program.methods
+      .createFootballMarket(
+        "Will home team win?",
+        "Team A",
+        "Team B",
+        startedKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+    const [startedMarket] = deriveMarketPda(program.programId, startedKey);
+
+    try {
+      await program.methods
+        .updateFeedChecks(null)
+        .accounts({
+          market: startedMarket,
+          authority: context.authority.publicKey,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("OracleSettingsLocked"));
+    }
+  });
+});
@@ -0,0 +1,204 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+import {
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+
+describe("Fixed Odds Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let authorityTokenAccount: PublicKey;
+  let marketPda: PublicKey;
+  const marketKey = "FIXED_ODDS_MARKET_001";
+
+  async function placeBet(amount: number, outcome: number) {
+    await program.methods
+      .placeBetOnOutcome(new anchor.BN(amount), outcome, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    authorityTokenAccount = getAssociatedTokenAddressSync(
+      context.mint,
+      context.authority.publicKey
+    );
+    try {
+      await createAssociatedTokenAccount(
+        context.provider.connection,
+        context.authority,
+        context.mint,
+        context.authority.publicKey
+      );
+    } catch (error) {
+      // Account might already exist, that's okay
+    }
+
+    await mintTo(
+      context.provider.connection,
+      context.authority,
+      context.mint,
+      authorityTokenAccount,
+      context.authority.publicKey,
+      1_000_000_000
+    );
+
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    await program.methods
+      .createFixedOddsMarket(
+        "Will the home side keep a clean sheet?",
+        marketKey,
+        ["Yes", "No"],
+        [new anchor.BN(20_000), new anchor.BN(30_000)],
+        [new anchor.BN(50_000_000), new anchor.BN(50_000_000)],
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+  });
+
+  it("refuses bets the house cannot cover", async () => {
+    try {
+      await placeBet(10_000_000, 0);
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InsufficientHouseLiquidity"));
+    }
+  });
+
+  it("locks the published odds on each bet", async () => {
+    await program.methods
+      .fundHouseLiquidity(new anchor.BN(100_000_000))
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    await placeBet(10_000_000, 0);
+
+    await program.methods
+      .updateFixedOdds([new anchor.BN(18_000), new anchor.BN(32_000)])
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    await placeBet(10_000_000, 0);
+
+    const [positionPda] = derivePositionPda(program.programId, marketPda, context.user1.publicKey);
+    const position = await program.account.position.fetch(positionPda);
+    assert.deepStrictEqual(
+      position.bets.map((bet) => bet.odds.toNumber()),
+      [20_000, 18_000]
+    );
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.liabilities[0].toNumber(), 38_000_000);
+  });
+
+  it("only lets the authority move the odds", async () => {
+    try {
+      await program.methods
+        .updateFixedOdds([new anchor.BN(50_000), new anchor.BN(50_000)])
+        .accounts({
+          market: marketPda,
+          authority: context.user2.publicKey,
+        } as any)
+        .signers([context.user2])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedBookmaker"));
+    }
+  });
+
+  it("enforces the per-outcome exposure cap", async () => {
+    try {
+      // 100 tokens at 3.2 would leave the house 200 short if it wins
+      await placeBet(100_000_000, 1);
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("ExposureCapExceeded"));
+    }
+  });
+
+  it("keeps the worst case liability in the vault", async () => {
+    // 120 in the vault, 38 owed if "Yes" wins
+    try {
+      await program.methods
+        .withdrawHouseLiquidity(new anchor.BN(90_000_000))
+        .accounts({
+          market: marketPda,
+          authority: context.authority.publicKey,
+          authorityTokenAccount: authorityTokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InsufficientHouseLiquidity"));
+    }
+
+    await program.methods
+      .withdrawHouseLiquidity(new anchor.BN(80_000_000))
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.collateral.toNumber(), 40_000_000);
+  });
+});
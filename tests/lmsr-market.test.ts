@@ -0,0 +1,290 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  deriveVaultPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+
+describe("LMSR Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let authorityTokenAccount: PublicKey;
+  let marketPda: PublicKey;
+  let vaultPda: PublicKey;
+  const marketKey = "LMSR_MARKET_001";
+  const liquidity = new anchor.BN(100_000_000); // b = 100 tokens
+  // b * ln(2), rounded up
+  const expectedSubsidy = Math.ceil(100_000_000 * Math.log(2));
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    authorityTokenAccount = getAssociatedTokenAddressSync(
+      context.mint,
+      context.authority.publicKey
+    );
+    try {
+      await createAssociatedTokenAccount(
+        context.provider.connection,
+        context.authority,
+        context.mint,
+        context.authority.publicKey
+      );
+    } catch (error) {
+      // Account might already exist, that's okay
+    }
+
+    await mintTo(
+      context.provider.connection,
+      context.authority,
+      context.mint,
+      authorityTokenAccount,
+      context.authority.publicKey,
+      1_000_000_000
+    );
+  });
+
+  it("creates a market funded with the maker's worst case loss", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+
+    await program.methods
+      .createLmsrMarket(
+        "Will it rain on match day?",
+        marketKey,
+        ["Yes", "No"],
+        liquidity,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+    [vaultPda] = deriveVaultPda(program.programId, marketPda);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.pricing.lmsr.liquidity.toString(), liquidity.toString());
+    assert.strictEqual(market.collateral.toNumber(), expectedSubsidy);
+
+    const vault = await getAccount(context.provider.connection, vaultPda);
+    assert.strictEqual(Number(vault.amount), expectedSubsidy);
+  });
+
+  it("rejects parimutuel bets", async () => {
+    try {
+      await program.methods
+        .placeBetOnOutcome(new anchor.BN(10_000_000), 0, null)
+        .accountsPartial({
+          market: marketPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnsupportedPricingMode"));
+    }
+  });
+
+  it("prices a buy off the cost function and moves the price", async () => {
+    const shares = new anchor.BN(50_000_000);
+    const before = await getAccount(context.provider.connection, context.user1TokenAccount);
+
+    await program.methods
+      .buyOutcomeShares(0, shares, new anchor.BN(50_000_000))
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    // C(q) = b * ln(e^(q0/b) + e^(q1/b))
+    const b = 100_000_000;
+    const expectedCost = Math.ceil(b * Math.log(Math.exp(0.5) + 1) - b * Math.log(2));
+    const expectedFee = Math.floor(expectedCost * 50 / 10_000);
+
+    const after = await getAccount(context.provider.connection, context.user1TokenAccount);
+    const paid = Number(before.amount - after.amount);
+    assert.ok(Math.abs(paid - (expectedCost + expectedFee)) <= 1);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.pools[0].toString(), shares.toString());
+
+    const [positionPda] = derivePositionPda(program.programId, marketPda, context.user1.publicKey);
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.amounts[0].toString(), shares.toString());
+  });
+
+  it("enforces the buyer's maximum cost", async () => {
+    try {
+      await program.methods
+        .buyOutcomeShares(0, new anchor.BN(50_000_000), new anchor.BN(1_000_000))
+        .accountsPartial({
+          market: marketPda,
+          user: context.user2.publicKey,
+          userTokenAccount: context.user2TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user2])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("SlippageExceeded"));
+    }
+  });
+
+  it("buys shares back before the start", async () => {
+    const [positionPda] = derivePositionPda(program.programId, marketPda, context.user1.publicKey);
+    const before = await getAccount(context.provider.connection, context.user1TokenAccount);
+
+    await program.methods
+      .sellOutcomeShares(0, new anchor.BN(20_000_000), new anchor.BN(0))
+      .accountsPartial({
+        market: marketPda,
+        position: positionPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const after = await getAccount(context.provider.connection, context.user1TokenAccount);
+    const proceeds = Number(after.amount - before.amount);
+    // Selling from 50 to 30 shares pays more than 0.5 but less than 1 per share
+    assert.ok(proceeds > 10_000_000 && proceeds < 20_000_000);
+
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.amounts[0].toString(), "30000000");
+
+    try {
+      await program.methods
+        .sellOutcomeShares(0, new anchor.BN(40_000_000), new anchor.BN(0))
+        .accountsPartial({
+          market: marketPda,
+          position: positionPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InsufficientShares"));
+    }
+  });
+
+  it("fails to sell once betting has closed", async () => {
+    // Starts in two hours, betting closed an hour ago
+    await program.methods
+      .updateBettingCutoff(new anchor.BN(3 * 3600))
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const [positionPda] = derivePositionPda(program.programId, marketPda, context.user1.publicKey);
+    try {
+      await program.methods
+        .sellOutcomeShares(0, new anchor.BN(10_000_000), new anchor.BN(0))
+        .accountsPartial({
+          market: marketPda,
+          position: positionPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("BettingClosed"));
+    }
+  });
+
+  it("refunds shares at their last price when voided and returns the surplus", async () => {
+    await program.methods
+      .cancelMarket()
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const [positionPda] = derivePositionPda(program.programId, marketPda, context.user1.publicKey);
+    await program.methods
+      .claimRefundFromMarket()
+      .accountsPartial({
+        market: marketPda,
+        position: positionPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    await program.methods
+      .withdrawLmsrLiquidity()
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    // Only uncollected fees are left behind
+    const market = await program.account.market.fetch(marketPda);
+    const vault = await getAccount(context.provider.connection, vaultPda);
+    assert.ok(Number(vault.amount) >= market.feesCollected.toNumber());
+  });
+});
@@ -0,0 +1,269 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import { getAccount } from "@solana/spl-token";
+import {
+  setupTestContext,
+  deriveMarketPda,
+  getImminentTimeValues,
+  waitForTimestamp,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+  AWAY_OUTCOME,
+  HOME_OUTCOME,
+} from "./utils";
+
+describe("Optimistic Resolution", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  let proposalPda: PublicKey;
+  let resolutionTime: anchor.BN;
+  const gameKey = "GAME_OPTIMISTIC_001";
+  const bond = new anchor.BN(10_000_000);
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    // The source has to be set before the start, proposals come after resolution time
+    const times = getImminentTimeValues(10);
+    resolutionTime = times.resolutionTime;
+    const { startTime, endTime } = times;
+    await program.methods
+      .createFootballMarket(
+        "Will home team win?",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveMarketPda(program.programId, gameKey);
+    [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), marketPda.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("fails to go optimistic without a bond", async () => {
+    try {
+      await program.methods
+        .updateOracleSource({
+          optimistic: {
+            bond: new anchor.BN(0),
+            challengeSeconds: new anchor.BN(3600),
+            arbiter: context.authority.publicKey,
+          },
+        })
+        .accounts({
+          market: marketPda,
+          authority: context.authority.publicKey,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidOptimisticConfig"));
+    }
+  });
+
+  it("goes optimistic before the start", async () => {
+    await program.methods
+      .updateOracleSource({
+        optimistic: {
+          bond,
+          challengeSeconds: new anchor.BN(3600),
+          arbiter: context.authority.publicKey,
+        },
+      })
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.oracleSource.optimistic.bond.toString(), bond.toString());
+  });
+
+  it("proposes a result by posting the bond", async () => {
+    await waitForTimestamp(context.provider.connection, resolutionTime);
+
+    const balanceBefore = await getAccount(context.provider.connection, context.user1TokenAccount);
+    await program.methods
+      .proposeResult(new anchor.BN(HOME_OUTCOME))
+      .accountsPartial({
+        market: marketPda,
+        proposer: context.user1.publicKey,
+        proposerTokenAccount: context.user1TokenAccount,
+        parentMarket: null,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+    const balanceAfter = await getAccount(context.provider.connection, context.user1TokenAccount);
+
+    assert.strictEqual((balanceBefore.amount - balanceAfter.amount).toString(), bond.toString());
+
+    const proposal = await program.account.proposal.fetch(proposalPda);
+    assert.ok(proposal.proposer.equals(context.user1.publicKey));
+    assert.strictEqual(proposal.result.toNumber(), HOME_OUTCOME);
+    assert.deepStrictEqual(proposal.status, { proposed: {} });
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.proposalOpen, true);
+  });
+
+  it("fails to change the oracle source once the market has started", async () => {
+    try {
+      await program.methods
+        .updateOracleSource({ switchboard: {} })
+        .accounts({
+          market: marketPda,
+          authority: context.authority.publicKey,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("OracleSettingsLocked"));
+    }
+  });
+
+  it("fails to finalize inside the challenge window", async () => {
+    try {
+      await program.methods
+        .finalizeProposal(null)
+        .accountsPartial({
+          market: marketPda,
+          proposerTokenAccount: context.user1TokenAccount,
+          disputerTokenAccount: null,
+          oracleFeed: null,
+          arbiter: null,
+          parentMarket: null,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("ChallengeWindowOpen"));
+    }
+  });
+
+  it("fails when the proposer disputes their own result", async () => {
+    try {
+      await program.methods
+        .disputeProposal()
+        .accountsPartial({
+          market: marketPda,
+          disputer: context.user1.publicKey,
+          disputerTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("SelfDispute"));
+    }
+  });
+
+  it("disputes the proposal with a matching bond", async () => {
+    await program.methods
+      .disputeProposal()
+      .accountsPartial({
+        market: marketPda,
+        disputer: context.user2.publicKey,
+        disputerTokenAccount: context.user2TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.user2])
+      .rpc();
+
+    const proposal = await program.account.proposal.fetch(proposalPda);
+    assert.ok(proposal.disputer.equals(context.user2.publicKey));
+    assert.deepStrictEqual(proposal.status, { disputed: {} });
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.disputed, true);
+    assert.strictEqual(market.resolved, false);
+  });
+
+  it("fails when someone other than the arbiter rules", async () => {
+    try {
+      await program.methods
+        .finalizeProposal(new anchor.BN(AWAY_OUTCOME))
+        .accountsPartial({
+          market: marketPda,
+          proposerTokenAccount: context.user1TokenAccount,
+          disputerTokenAccount: context.user2TokenAccount,
+          oracleFeed: null,
+          arbiter: context.user1.publicKey,
+          parentMarket: null,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedArbiter"));
+    }
+  });
+
+  it("settles on the arbiter's ruling and pays both bonds to the disputer", async () => {
+    const balanceBefore = await getAccount(context.provider.connection, context.user2TokenAccount);
+    await program.methods
+      .finalizeProposal(new anchor.BN(AWAY_OUTCOME))
+      .accountsPartial({
+        market: marketPda,
+        proposerTokenAccount: context.user1TokenAccount,
+        disputerTokenAccount: context.user2TokenAccount,
+        oracleFeed: null,
+        arbiter: context.authority.publicKey,
+        parentMarket: null,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+    const balanceAfter = await getAccount(context.provider.connection, context.user2TokenAccount);
+
+    assert.strictEqual(
+      (balanceAfter.amount - balanceBefore.amount).toString(),
+      bond.muln(2).toString()
+    );
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.resolved, true);
+    assert.strictEqual(market.winningOutcome, AWAY_OUTCOME);
+    assert.strictEqual(market.outcome, false);
+    assert.strictEqual(market.isDraw, false);
+    assert.strictEqual(market.disputed, false);
+    assert.strictEqual(market.proposalOpen, false);
+
+    const proposal = await program.account.proposal.fetch(proposalPda);
+    assert.deepStrictEqual(proposal.status, { settled: {} });
+  });
+});
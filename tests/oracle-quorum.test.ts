@@ -0,0 +1,175 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveMarketPda,
+  getImminentTimeValues,
+  waitForTimestamp,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+
+describe("Oracle Quorum", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  let resolutionTime: anchor.BN;
+  const gameKey = "GAME_ORACLE_QUORUM";
+  const feeds = [generateOracleFeedHash(), generateOracleFeedHash(), generateOracleFeedHash()];
+
+  async function setOracleQuorum(quorumFeeds: number[][], threshold: number) {
+    await program.methods
+      .updateOracleQuorum(quorumFeeds, threshold)
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    // The quorum has to be set before the start, resolving comes after resolution time
+    const times = getImminentTimeValues(10);
+    resolutionTime = times.resolutionTime;
+    const { startTime, endTime } = times;
+    await program.methods
+      .createFootballMarket(
+        "Will home team win?",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveMarketPda(program.programId, gameKey);
+  });
+
+  it("fails with a threshold below a majority", async () => {
+    try {
+      await setOracleQuorum(feeds, 1);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidOracleQuorum"));
+    }
+  });
+
+  it("fails with the same feed listed twice", async () => {
+    try {
+      await setOracleQuorum([feeds[0], feeds[1], feeds[0]], 2);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidOracleQuorum"));
+    }
+  });
+
+  it("fails when not the market authority", async () => {
+    try {
+      await program.methods
+        .updateOracleQuorum(feeds, 2)
+        .accounts({
+          market: marketPda,
+          authority: context.user1.publicKey,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedUpdater"));
+    }
+  });
+
+  it("fails to switch to a quorum without its feeds", async () => {
+    try {
+      await program.methods
+        .updateOracleSource({ quorum: { threshold: 2 } })
+        .accounts({
+          market: marketPda,
+          authority: context.authority.publicKey,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidOracleSource"));
+    }
+  });
+
+  it("stores a quorum of 2 of 3 feeds", async () => {
+    await setOracleQuorum(feeds, 2);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.deepStrictEqual(market.oracleSource, { quorum: { threshold: 2 } });
+    assert.strictEqual(market.quorumFeeds.length, 3);
+    assert.deepStrictEqual(market.oracleReports, [null, null, null]);
+    assert.strictEqual(market.disputed, false);
+  });
+
+  it("fails to change the quorum once the market has started", async () => {
+    await waitForTimestamp(context.provider.connection, resolutionTime);
+
+    try {
+      await setOracleQuorum([feeds[0], feeds[1]], 2);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("OracleSettingsLocked"));
+    }
+  });
+
+  it("fails to resolve before enough feeds report", async () => {
+    try {
+      await program.methods
+        .resolveMarket()
+        .accounts({
+          market: marketPda,
+        } as any)
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("QuorumNotReached"));
+    }
+  });
+
+  it("fails with a feed that is not one of the market's", async () => {
+    try {
+      await program.methods
+        .resolveMarket()
+        .accounts({
+          market: marketPda,
+        } as any)
+        .remainingAccounts([
+          { pubkey: Keypair.generate().publicKey, isWritable: false, isSigner: false },
+        ])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidFeed"));
+    }
+  });
+});
@@ -0,0 +1,284 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import {
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveMarketPda,
+  getTimeValues,
+  getImminentTimeValues,
+  waitForTimestamp,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+  HOME_OUTCOME,
+} from "./utils";
+
+// The signed message is the market address followed by the result as a
+// little-endian i64
+function attestationMessage(market: PublicKey, result: number): Buffer {
+  const value = Buffer.alloc(8);
+  value.writeBigInt64LE(BigInt(result));
+  return Buffer.concat([market.toBuffer(), value]);
+}
+
+describe("Oracle Source", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let oracleSignerPda: PublicKey;
+  const sportsData = Keypair.generate();
+
+  async function createMarket(
+    gameKey: string,
+    { startTime, endTime, resolutionTime }: ReturnType<typeof getTimeValues>
+  ): Promise<PublicKey> {
+    await program.methods
+      .createFootballMarket(
+        "Will home team win?",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    return deriveMarketPda(program.programId, gameKey)[0];
+  }
+
+  async function setOracleSource(market: PublicKey, oracleSource: any) {
+    await program.methods
+      .updateOracleSource(oracleSource)
+      .accounts({
+        market,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    [oracleSignerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_signer"), sportsData.publicKey.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("fails to register a signer when not the protocol admin", async () => {
+    try {
+      await program.methods
+        .registerOracleSigner(sportsData.publicKey)
+        .accountsPartial({
+          admin: context.user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedProtocolAdmin"));
+    }
+  });
+
+  it("registers a sports-data signer", async () => {
+    await program.methods
+      .registerOracleSigner(sportsData.publicKey)
+      .accountsPartial({
+        admin: context.authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const oracleSigner = await program.account.oracleSigner.fetch(oracleSignerPda);
+    assert.ok(oracleSigner.signer.equals(sportsData.publicKey));
+  });
+
+  it("fails to change the oracle source when not the market authority", async () => {
+    const marketPda = await createMarket("GAME_ORACLE_UNAUTHORIZED", getTimeValues(2));
+
+    try {
+      await program.methods
+        .updateOracleSource({ signedAttestation: { signer: context.user1.publicKey } })
+        .accounts({
+          market: marketPda,
+          authority: context.user1.publicKey,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedUpdater"));
+    }
+  });
+
+  it("fails to change the oracle source once the market has started", async () => {
+    // Kicked off four hours ago
+    const marketPda = await createMarket("GAME_ORACLE_STARTED", getTimeValues(-4));
+
+    try {
+      await setOracleSource(marketPda, { signedAttestation: { signer: sportsData.publicKey } });
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("OracleSettingsLocked"));
+    }
+  });
+
+  describe("after the resolution time", () => {
+    let attestedMarket: PublicKey;
+    let impostorMarket: PublicKey;
+    let replayedMarket: PublicKey;
+    let priceMarket: PublicKey;
+    let foreignPriceMarket: PublicKey;
+
+    before(async () => {
+      // Sources have to be set before the start, then resolved once it has passed
+      const times = getImminentTimeValues(20);
+      attestedMarket = await createMarket("GAME_ORACLE_ATTESTED", times);
+      impostorMarket = await createMarket("GAME_ORACLE_IMPOSTOR", times);
+      replayedMarket = await createMarket("GAME_ORACLE_REPLAYED", times);
+      priceMarket = await createMarket("GAME_ORACLE_PRICE", times);
+      foreignPriceMarket = await createMarket("GAME_ORACLE_PRICE_OWNER", times);
+
+      for (const marketPda of [attestedMarket, impostorMarket, replayedMarket]) {
+        await setOracleSource(marketPda, { signedAttestation: { signer: sportsData.publicKey } });
+      }
+      await setOracleSource(priceMarket, {
+        priceAccount: { account: Keypair.generate().publicKey },
+      });
+      // The mint exists but belongs to the token program
+      await setOracleSource(foreignPriceMarket, {
+        priceAccount: { account: context.mint },
+      });
+
+      await waitForTimestamp(context.provider.connection, times.resolutionTime);
+    });
+
+    it("resolves a market from a signed attestation", async () => {
+      const market = await program.account.market.fetch(attestedMarket);
+      assert.ok(market.oracleSource.signedAttestation.signer.equals(sportsData.publicKey));
+
+      await program.methods
+        .resolveMarket()
+        .accounts({
+          market: attestedMarket,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          oracleSigner: oracleSignerPda,
+        } as any)
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: sportsData.secretKey,
+            message: attestationMessage(attestedMarket, HOME_OUTCOME),
+          }),
+        ])
+        .rpc();
+
+      const resolved = await program.account.market.fetch(attestedMarket);
+      assert.strictEqual(resolved.resolved, true);
+      assert.strictEqual(resolved.winningOutcome, HOME_OUTCOME);
+      assert.strictEqual(resolved.outcome, true);
+      assert.strictEqual(resolved.finalResultValue.toString(), HOME_OUTCOME.toString());
+    });
+
+    it("fails with an attestation from another signer", async () => {
+      try {
+        await program.methods
+          .resolveMarket()
+          .accounts({
+            market: impostorMarket,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            oracleSigner: oracleSignerPda,
+          } as any)
+          .preInstructions([
+            Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: Keypair.generate().secretKey,
+              message: attestationMessage(impostorMarket, HOME_OUTCOME),
+            }),
+          ])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        assert.ok(error.message.includes("UnregisteredOracleSigner"));
+      }
+    });
+
+    it("fails with an attestation signed for another market", async () => {
+      try {
+        await program.methods
+          .resolveMarket()
+          .accounts({
+            market: replayedMarket,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            oracleSigner: oracleSignerPda,
+          } as any)
+          .preInstructions([
+            Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: sportsData.secretKey,
+              message: attestationMessage(Keypair.generate().publicKey, HOME_OUTCOME),
+            }),
+          ])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        assert.ok(error.message.includes("InvalidAttestation"));
+      }
+    });
+
+    it("fails when the price account is not the market's", async () => {
+      try {
+        await program.methods
+          .resolveMarket()
+          .accounts({
+            market: priceMarket,
+            oracleFeed: Keypair.generate().publicKey,
+          } as any)
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        assert.ok(error.message.includes("InvalidFeed"));
+      }
+    });
+
+    it("fails when the price account is not owned by the price program", async () => {
+      try {
+        await program.methods
+          .resolveMarket()
+          .accounts({
+            market: foreignPriceMarket,
+            oracleFeed: context.mint,
+          } as any)
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        assert.ok(error.message.includes("InvalidPriceAccountOwner"));
+      }
+    });
+  });
+});
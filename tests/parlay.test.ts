@@ -0,0 +1,289 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveMarketPda,
+  deriveParlayPoolPda,
+  deriveParlayVaultPda,
+  deriveParlayPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  HOME_OUTCOME,
+  AWAY_OUTCOME,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+import {
+  mintTo,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccount,
+} from "@solana/spl-token";
+
+describe("Parlay", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let parlayPoolPda: PublicKey;
+  let parlayVaultPda: PublicKey;
+  let parlayPda: PublicKey;
+  let authorityTokenAccount: PublicKey;
+  const legMarkets: PublicKey[] = [];
+  const parlayId = new anchor.BN(1);
+  const stake = new anchor.BN(10_000_000); // 10 tokens
+  const minLegPool = new anchor.BN(50_000_000);
+  const marginBps = 500;
+
+  async function openParlay(id: anchor.BN, outcomes: number[], markets: PublicKey[]) {
+    await program.methods
+      .openParlay(id, stake, Buffer.from(outcomes))
+      .accountsPartial({
+        parlayPool: parlayPoolPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(
+        markets.map((market) => ({
+          pubkey: market,
+          isWritable: false,
+          isSigner: false,
+        }))
+      )
+      .signers([context.user1])
+      .rpc();
+  }
+
+  async function createPricedMarket(
+    gameKey: string,
+    stakePerSide: number = 50_000_000
+  ): Promise<PublicKey> {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+
+    await program.methods
+      .createFootballMarket(
+        "Parlay leg",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const [marketPda] = deriveMarketPda(program.programId, gameKey);
+
+    // Equal pools on home and away price each side at 2.0
+    for (const betType of [{ home: {} }, { away: {} }]) {
+      await program.methods
+        .placeBetOnMarket(new anchor.BN(stakePerSide), betType, null)
+        .accountsPartial({
+          market: marketPda,
+          user: context.user2.publicKey,
+          userTokenAccount: context.user2TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user2])
+        .rpc();
+    }
+
+    return marketPda;
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    await program.methods
+      .createParlayPool(new anchor.BN(1_000_000_000), minLegPool, marginBps)
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [parlayPoolPda] = deriveParlayPoolPda(
+      program.programId,
+      context.authority.publicKey,
+      context.mint
+    );
+    [parlayVaultPda] = deriveParlayVaultPda(program.programId, parlayPoolPda);
+    [parlayPda] = deriveParlayPda(
+      program.programId,
+      parlayPoolPda,
+      context.user1.publicKey,
+      parlayId
+    );
+
+    // House liquidity underwriting the tickets
+    await mintTo(
+      context.provider.connection,
+      context.authority,
+      context.mint,
+      parlayVaultPda,
+      context.authority.publicKey,
+      100_000_000
+    );
+
+    authorityTokenAccount = getAssociatedTokenAddressSync(
+      context.mint,
+      context.authority.publicKey
+    );
+    try {
+      await createAssociatedTokenAccount(
+        context.provider.connection,
+        context.authority,
+        context.mint,
+        context.authority.publicKey
+      );
+    } catch (error) {
+      // Account might already exist, that's okay
+    }
+
+    legMarkets.push(await createPricedMarket("GAME_PARLAY_LEG_1"));
+    legMarkets.push(await createPricedMarket("GAME_PARLAY_LEG_2"));
+  });
+
+  it("opens a parlay and reserves its maximum payout", async () => {
+    await openParlay(parlayId, [HOME_OUTCOME, AWAY_OUTCOME], legMarkets);
+
+    // 2.0 less the 5% margin
+    const parlay = await program.account.parlay.fetch(parlayPda);
+    assert.strictEqual(parlay.legs.length, 2);
+    assert.strictEqual(parlay.legs[0].odds.toString(), "19000");
+    assert.strictEqual(parlay.legs[1].odds.toString(), "19000");
+    assert.deepStrictEqual(parlay.status, { open: {} });
+
+    // 10 tokens at 1.9 x 1.9
+    const expectedMaxPayout = new anchor.BN(36_100_000);
+    assert.strictEqual(parlay.maxPayout.toString(), expectedMaxPayout.toString());
+
+    const pool = await program.account.parlayPool.fetch(parlayPoolPda);
+    assert.strictEqual(pool.reserved.toString(), expectedMaxPayout.toString());
+  });
+
+  it("fails with a single leg", async () => {
+    try {
+      await openParlay(new anchor.BN(2), [HOME_OUTCOME], [legMarkets[0]]);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidParlayLegs"));
+    }
+  });
+
+  it("fails with a leg whose pools are too thin to price", async () => {
+    const thinMarket = await createPricedMarket("GAME_PARLAY_THIN", 1_000_000);
+
+    try {
+      await openParlay(new anchor.BN(3), [HOME_OUTCOME, HOME_OUTCOME], [legMarkets[1], thinMarket]);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("LegNotPriceable"));
+    }
+  });
+
+  it("fails with a leg whose betting has closed", async () => {
+    const closedMarket = await createPricedMarket("GAME_PARLAY_CLOSED");
+    // Starts in two hours, betting closed an hour ago
+    await program.methods
+      .updateBettingCutoff(new anchor.BN(3 * 3600))
+      .accounts({
+        market: closedMarket,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    try {
+      await openParlay(new anchor.BN(4), [HOME_OUTCOME, HOME_OUTCOME], [legMarkets[1], closedMarket]);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("BettingClosed"));
+    }
+  });
+
+  it("fails to settle a leg whose market is still open", async () => {
+    try {
+      await program.methods
+        .settleParlayLeg(1)
+        .accounts({
+          parlay: parlayPda,
+          parlayPool: parlayPoolPda,
+          market: legMarkets[1],
+        } as any)
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("MarketNotResolved"));
+    }
+  });
+
+  it("voids a leg when its market is cancelled", async () => {
+    await program.methods
+      .cancelMarket()
+      .accounts({
+        market: legMarkets[0],
+        authority: context.authority.publicKey,
+        authorityTokenAccount: authorityTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    await program.methods
+      .settleParlayLeg(0)
+      .accounts({
+        parlay: parlayPda,
+        parlayPool: parlayPoolPda,
+        market: legMarkets[0],
+      } as any)
+      .rpc();
+
+    const parlay = await program.account.parlay.fetch(parlayPda);
+    assert.deepStrictEqual(parlay.legs[0].status, { void: {} });
+    assert.deepStrictEqual(parlay.legs[1].status, { pending: {} });
+    // A void leg counts as odds of 1.0
+    assert.strictEqual(parlay.legs[0].factor.toString(), "10000");
+    assert.strictEqual(parlay.payout.toString(), stake.toString());
+    assert.deepStrictEqual(parlay.status, { open: {} });
+  });
+
+  it("does not let the house withdraw reserved liquidity", async () => {
+    try {
+      await program.methods
+        .withdrawParlayLiquidity(new anchor.BN(100_000_000))
+        .accounts({
+          parlayPool: parlayPoolPda,
+          authority: context.authority.publicKey,
+          authorityTokenAccount: authorityTokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InsufficientParlayLiquidity"));
+    }
+  });
+});
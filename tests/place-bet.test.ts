@@ -10,6 +10,10 @@ import {
   derivePositionPda,
   getTimeValues,
   generateOracleFeedHash,
+  HOME_OUTCOME,
+  AWAY_OUTCOME,
+  DRAW_OUTCOME,
+  DEFAULT_FEE_BPS,
 } from "./utils";
 import { getAccount, getAssociatedTokenAddressSync } from "@solana/spl-token";
 
@@ -38,6 +42,8 @@ describe("Place Bet", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -71,7 +77,7 @@ describe("Place Bet", () => {
     );
 
     const tx = await program.methods
-      .placeBetOnMarket(betAmount, { home: {} })
+      .placeBetOnMarket(betAmount, { home: {} }, null)
       .accountsPartial({
         market: marketPda,
         user: context.user1.publicKey,
@@ -84,17 +90,17 @@ describe("Place Bet", () => {
 
     // Verify market pools updated
     const market = await program.account.market.fetch(marketPda);
-    assert.strictEqual(market.yesPool.toString(), betAmount.toString());
-    assert.strictEqual(market.noPool.toString(), "0");
-    assert.strictEqual(market.drawPool.toString(), "0");
+    assert.strictEqual(market.pools[HOME_OUTCOME].toString(), betAmount.toString());
+    assert.strictEqual(market.pools[AWAY_OUTCOME].toString(), "0");
+    assert.strictEqual(market.pools[DRAW_OUTCOME].toString(), "0");
 
     // Verify position created
     const position = await program.account.position.fetch(positionPda);
     assert.strictEqual(position.user.toString(), context.user1.publicKey.toString());
     assert.strictEqual(position.market.toString(), marketPda.toString());
-    assert.strictEqual(position.yesAmount.toString(), betAmount.toString());
-    assert.strictEqual(position.noAmount.toString(), "0");
-    assert.strictEqual(position.drawAmount.toString(), "0");
+    assert.strictEqual(position.amounts[HOME_OUTCOME].toString(), betAmount.toString());
+    assert.strictEqual(position.amounts[AWAY_OUTCOME].toString(), "0");
+    assert.strictEqual(position.amounts[DRAW_OUTCOME].toString(), "0");
 
     // Verify token balances (fee is 0.5% = 50 basis points)
     const feeAmount = betAmount.mul(new anchor.BN(50)).div(new anchor.BN(10_000));
@@ -124,7 +130,7 @@ describe("Place Bet", () => {
     );
 
     const tx = await program.methods
-      .placeBetOnMarket(betAmount, { away: {} })
+      .placeBetOnMarket(betAmount, { away: {} }, null)
       .accountsPartial({
         market: marketPda,
         user: context.user2.publicKey,
@@ -137,13 +143,13 @@ describe("Place Bet", () => {
 
     // Verify market pools updated
     const market = await program.account.market.fetch(marketPda);
-    assert.strictEqual(market.noPool.toString(), betAmount.toString());
+    assert.strictEqual(market.pools[AWAY_OUTCOME].toString(), betAmount.toString());
 
     // Verify position created
     const position = await program.account.position.fetch(positionPda);
-    assert.strictEqual(position.noAmount.toString(), betAmount.toString());
-    assert.strictEqual(position.yesAmount.toString(), "0");
-    assert.strictEqual(position.drawAmount.toString(), "0");
+    assert.strictEqual(position.amounts[AWAY_OUTCOME].toString(), betAmount.toString());
+    assert.strictEqual(position.amounts[HOME_OUTCOME].toString(), "0");
+    assert.strictEqual(position.amounts[DRAW_OUTCOME].toString(), "0");
   });
 
   it("places a bet on Draw successfully", async () => {
@@ -155,7 +161,7 @@ describe("Place Bet", () => {
     );
 
     const tx = await program.methods
-      .placeBetOnMarket(betAmount, { draw: {} })
+      .placeBetOnMarket(betAmount, { draw: {} }, null)
       .accountsPartial({
         market: marketPda,
         user: context.user1.publicKey,
@@ -168,19 +174,19 @@ describe("Place Bet", () => {
 
     // Verify market pools updated
     const market = await program.account.market.fetch(marketPda);
-    assert.strictEqual(market.drawPool.toString(), betAmount.toString());
+    assert.strictEqual(market.pools[DRAW_OUTCOME].toString(), betAmount.toString());
 
     // Verify position updated (user1 already had a Home bet)
     const position = await program.account.position.fetch(positionPda);
-    assert.strictEqual(position.drawAmount.toString(), betAmount.toString());
+    assert.strictEqual(position.amounts[DRAW_OUTCOME].toString(), betAmount.toString());
     // Previous Home bet should still be there
-    assert.ok(position.yesAmount.gt(new anchor.BN(0)));
+    assert.ok(position.amounts[HOME_OUTCOME].gt(new anchor.BN(0)));
   });
 
   it("fails when placing bet with zero amount", async () => {
     try {
       await program.methods
-        .placeBetOnMarket(new anchor.BN(0), { home: {} })
+        .placeBetOnMarket(new anchor.BN(0), { home: {} }, null)
         .accountsPartial({
           market: marketPda,
           user: context.user1.publicKey,
@@ -214,6 +220,8 @@ describe("Place Bet", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -228,7 +236,7 @@ describe("Place Bet", () => {
 
     try {
       await program.methods
-        .placeBetOnMarket(new anchor.BN(100_000_000), { home: {} })
+        .placeBetOnMarket(new anchor.BN(100_000_000), { home: {} }, null)
         .accountsPartial({
           market: immediateMarketPda,
           user: context.user1.publicKey,
@@ -247,4 +255,125 @@ describe("Place Bet", () => {
       );
     }
   });
+
+  it("fails when the projected payout is below the bettor's limit", async () => {
+    try {
+      await program.methods
+        // Nothing on this market can pay 100x
+        .placeBetOnMarket(new anchor.BN(10_000_000), { home: {} }, new anchor.BN(1_000_000))
+        .accountsPartial({
+          market: marketPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("PayoutBelowLimit"));
+    }
+  });
+
+  it("places a bet within the bettor's payout limit", async () => {
+    const marketBefore = await program.account.market.fetch(marketPda);
+    const betAmount = new anchor.BN(10_000_000);
+
+    await program.methods
+      .placeBetOnMarket(betAmount, { away: {} }, new anchor.BN(10_000))
+      .accountsPartial({
+        market: marketPda,
+        user: context.user2.publicKey,
+        userTokenAccount: context.user2TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user2])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(
+      market.pools[AWAY_OUTCOME].toString(),
+      marketBefore.pools[AWAY_OUTCOME].add(betAmount).toString()
+    );
+  });
+
+  it("fails inside the late-betting cutoff window", async () => {
+    const cutoffGameKey = "GAME_CUTOFF";
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+
+    await program.methods
+      .createFootballMarket(
+        "Cutoff market",
+        "Team X",
+        "Team Y",
+        cutoffGameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const [cutoffMarketPda] = deriveMarketPda(program.programId, cutoffGameKey);
+
+    // Betting closes three hours before a start two hours away
+    await program.methods
+      .updateBettingCutoff(new anchor.BN(3 * 3600))
+      .accounts({
+        market: cutoffMarketPda,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    const market = await program.account.market.fetch(cutoffMarketPda);
+    assert.strictEqual(market.bettingCutoff.toString(), (3 * 3600).toString());
+
+    try {
+      await program.methods
+        .placeBetOnMarket(new anchor.BN(10_000_000), { home: {} }, null)
+        .accountsPartial({
+          market: cutoffMarketPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("BettingClosed"));
+    }
+  });
+
+  it("fails to change the cutoff when not the market authority", async () => {
+    try {
+      await program.methods
+        .updateBettingCutoff(new anchor.BN(600))
+        .accounts({
+          market: marketPda,
+          authority: context.user1.publicKey,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedBettingWindow"));
+    }
+  });
 });
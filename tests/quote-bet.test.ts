@@ -0,0 +1,148 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  deriveUserVolumePda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+
+describe("Quote Bet", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  const marketKey = "QUOTE_MARKET_001";
+
+  async function quote(outcome: number, amount: number) {
+    return await program.methods
+      .quoteBet(outcome, new anchor.BN(amount))
+      .accountsPartial({
+        market: marketPda,
+        bettor: context.user1.publicKey,
+        userVolume: null,
+      } as any)
+      .view();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    await program.methods
+      .createCategoricalMarket(
+        "Who will win the derby?",
+        marketKey,
+        ["Team A", "Team B", "Draw", "Abandoned"],
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+  });
+
+  it("quotes an even market before any bets", async () => {
+    const result = await quote(0, 100_000_000);
+
+    assert.strictEqual(result.feeBps, DEFAULT_FEE_BPS);
+    assert.strictEqual(result.fee.toString(), "500000");
+    // Alone in the pot, the bet only gets itself back
+    assert.strictEqual(result.projectedPayout.toString(), "100000000");
+    assert.deepStrictEqual(
+      result.impliedProbabilities.map((p: anchor.BN) => p.toNumber()),
+      [2_500, 2_500, 2_500, 2_500]
+    );
+  });
+
+  it("quotes at the current pools without changing them", async () => {
+    await program.methods
+      .placeBetOnOutcome(new anchor.BN(300_000_000), 0, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+    await program.methods
+      .placeBetOnOutcome(new anchor.BN(100_000_000), 1, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user2.publicKey,
+        userTokenAccount: context.user2TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user2])
+      .rpc();
+
+    // 100 more on Team B makes a 500 pot over a 200 pool
+    const result = await quote(1, 100_000_000);
+    assert.strictEqual(result.projectedPayout.toString(), "250000000");
+    assert.deepStrictEqual(
+      result.impliedProbabilities.map((p: anchor.BN) => p.toNumber()),
+      [7_500, 2_500, 0, 0]
+    );
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.pools[1].toString(), "100000000");
+  });
+
+  it("fails to quote an outcome the market does not have", async () => {
+    try {
+      await quote(4, 100_000_000);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidOutcome"));
+    }
+  });
+
+  it("fails to quote with another user's volume account", async () => {
+    await program.methods
+      .createUserVolume()
+      .accountsPartial({
+        user: context.user2.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user2])
+      .rpc();
+
+    try {
+      await program.methods
+        .quoteBet(0, new anchor.BN(100_000_000))
+        .accountsPartial({
+          market: marketPda,
+          bettor: context.user1.publicKey,
+          userVolume: deriveUserVolumePda(program.programId, context.user2.publicKey)[0],
+        } as any)
+        .view();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("ConstraintSeeds"));
+    }
+  });
+});
@@ -8,6 +8,7 @@ import {
   deriveMarketPda,
   getTimeValues,
   generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
 } from "./utils";
 
 // Helper function to create a mock Switchboard oracle feed account
@@ -57,6 +58,8 @@ describe("Resolve Market", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -107,6 +110,8 @@ describe("Resolve Market", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -124,6 +129,7 @@ describe("Resolve Market", () => {
     assert.strictEqual(market.resolved, false);
     assert.strictEqual(market.outcome, null);
     assert.strictEqual(market.isDraw, false);
+    assert.strictEqual(market.refundMode, false);
 
     // Note: To properly test "already resolved", we would need to resolve it first
     // But since we can't easily create a valid Switchboard feed, we verify the initial state
@@ -145,6 +151,8 @@ describe("Resolve Market", () => {
         endTime,
         resolutionTime,
         oracleFeedHash,
+        { matchResult: {} },
+        DEFAULT_FEE_BPS,
       )
       .accountsPartial({
         authority: context.authority.publicKey,
@@ -0,0 +1,199 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  deriveTotalGoalsMarketPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+  DEFAULT_FEE_BPS,
+} from "./utils";
+
+describe("Time Weighted Market", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  const marketKey = "TIME_WEIGHTED_MARKET_001";
+
+  async function setTimeWeighting(timeWeighting: any) {
+    await program.methods
+      .updateTimeWeighting(timeWeighting)
+      .accounts({
+        market: marketPda,
+        authority: context.authority.publicKey,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    await program.methods
+      .createCategoricalMarket(
+        "Who will win the title?",
+        marketKey,
+        ["Team A", "Team B"],
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+  });
+
+  it("fails with a bonus above the protocol maximum", async () => {
+    try {
+      await setTimeWeighting({ maxBonusBps: 10_001, rampSeconds: new anchor.BN(3600) });
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidTimeWeighting"));
+    }
+  });
+
+  it("fails when not the market authority", async () => {
+    try {
+      await program.methods
+        .updateTimeWeighting({ maxBonusBps: 5_000, rampSeconds: new anchor.BN(3600) })
+        .accounts({
+          market: marketPda,
+          authority: context.user1.publicKey,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("UnauthorizedTimeWeighting"));
+    }
+  });
+
+  it("turns on time weighting before any bets", async () => {
+    await setTimeWeighting({ maxBonusBps: 10_000, rampSeconds: new anchor.BN(3600) });
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.timeWeighting.maxBonusBps, 10_000);
+    assert.strictEqual(market.timeWeighting.rampSeconds.toString(), "3600");
+    assert.deepStrictEqual(market.shares.map((s: anchor.BN) => s.toString()), ["0", "0"]);
+  });
+
+  it("gives a stake placed before the ramp the full bonus in shares", async () => {
+    const betAmount = new anchor.BN(100_000_000);
+    await program.methods
+      .placeBetOnOutcome(betAmount, 0, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    // Two hours out is past the one hour ramp, so every token earns two shares
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.pools[0].toString(), betAmount.toString());
+    assert.strictEqual(market.shares[0].toString(), betAmount.muln(2).toString());
+
+    const [positionPda] = derivePositionPda(
+      program.programId,
+      marketPda,
+      context.user1.publicKey
+    );
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.amounts[0].toString(), betAmount.toString());
+    assert.strictEqual(position.shares[0].toString(), betAmount.muln(2).toString());
+  });
+
+  it("quotes the payout by shares", async () => {
+    const result = await program.methods
+      .quoteBet(0, new anchor.BN(100_000_000))
+      .accountsPartial({
+        market: marketPda,
+        bettor: context.user1.publicKey,
+        userVolume: null,
+      } as any)
+      .view();
+
+    // 200 pot shared over 400 shares, half of them the new stake's
+    assert.strictEqual(result.projectedPayout.toString(), "100000000");
+  });
+
+  it("fails to change the mode once bets are in", async () => {
+    try {
+      await setTimeWeighting(null);
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("BetsAlreadyPlaced"));
+    }
+  });
+
+  it("fails on a market that can settle as a push", async () => {
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    const gameKey = "TIME_WEIGHTED_TOTALS_001";
+    // A whole goal line refunds both sides when the total lands on it
+    const line = 200;
+    const [totalsPda] = deriveTotalGoalsMarketPda(program.programId, gameKey, line);
+
+    await program.methods
+      .createFootballMarket(
+        "Over/Under 2 goals",
+        "Team A",
+        "Team B",
+        gameKey,
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        { totalGoals: { line } },
+        DEFAULT_FEE_BPS,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        market: totalsPda,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    try {
+      await program.methods
+        .updateTimeWeighting({ maxBonusBps: 5_000, rampSeconds: new anchor.BN(3600) })
+        .accounts({
+          market: totalsPda,
+          authority: context.authority.publicKey,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+
+      assert.fail("Should have thrown an error");
+    } catch (error: any) {
+      assert.ok(error.message.includes("InvalidMarketType"));
+    }
+  });
+});
@@ -0,0 +1,212 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  setupTestContext,
+  deriveCreatorMarketPda,
+  derivePositionPda,
+  getTimeValues,
+  generateOracleFeedHash,
+} from "./utils";
+import {
+  mintTo,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccount,
+} from "@solana/spl-token";
+
+describe("User Volume", () => {
+  let context: Awaited<ReturnType<typeof setupTestContext>>;
+  let program: Program<PredictionMarket>;
+  let marketPda: PublicKey;
+  let userVolumePda: PublicKey;
+  const marketKey = "VOLUME_MARKET_001";
+  const marketFeeBps = 200;
+
+  async function placeBet(amount: number, withVolume: boolean) {
+    await program.methods
+      .placeBetOnOutcome(new anchor.BN(amount), 0, null)
+      .accountsPartial({
+        market: marketPda,
+        user: context.user1.publicKey,
+        userTokenAccount: context.user1TokenAccount,
+        userVolume: withVolume ? userVolumePda : null,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+  }
+
+  before(async () => {
+    context = await setupTestContext();
+    program = context.program;
+
+    // Enough for the first tier plus its fee
+    await mintTo(
+      context.provider.connection,
+      context.authority,
+      context.mint,
+      context.user1TokenAccount,
+      context.authority.publicKey,
+      1_000_000_000
+    );
+
+    const { startTime, endTime, resolutionTime } = getTimeValues(2);
+    await program.methods
+      .createCategoricalMarket(
+        "Who will top the table?",
+        marketKey,
+        ["Team A", "Team B"],
+        startTime,
+        endTime,
+        resolutionTime,
+        generateOracleFeedHash(),
+        marketFeeBps,
+      )
+      .accountsPartial({
+        authority: context.authority.publicKey,
+        mint: context.mint,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.authority])
+      .rpc();
+
+    [marketPda] = deriveCreatorMarketPda(
+      program.programId,
+      context.authority.publicKey,
+      marketKey
+    );
+    [userVolumePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_volume"), context.user1.publicKey.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("opens a volume account for a user", async () => {
+    await program.methods
+      .createUserVolume()
+      .accountsPartial({
+        user: context.user1.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([context.user1])
+      .rpc();
+
+    const userVolume = await program.account.userVolume.fetch(userVolumePda);
+    assert.ok(userVolume.user.equals(context.user1.publicKey));
+    assert.strictEqual(userVolume.cumulativeStake.toString(), "0");
+  });
+
+  it("charges the full market fee below the first tier", async () => {
+    const amount = 1_000_000_000;
+    await placeBet(amount, true);
+
+    const userVolume = await program.account.userVolume.fetch(userVolumePda);
+    assert.strictEqual(userVolume.cumulativeStake.toString(), amount.toString());
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.feeBps, marketFeeBps);
+    assert.strictEqual(market.feesCollected.toString(), "20000000");
+  });
+
+  it("discounts the fee once the first tier is reached", async () => {
+    await placeBet(10_000_000, true);
+
+    // 90% of 200 bps
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(market.feesCollected.toString(), (20_000_000 + 180_000).toString());
+
+    const [positionPda] = derivePositionPda(
+      program.programId,
+      marketPda,
+      context.user1.publicKey
+    );
+    const position = await program.account.position.fetch(positionPda);
+    assert.strictEqual(position.feesPaid.toString(), (20_000_000 + 180_000).toString());
+
+    const userVolume = await program.account.userVolume.fetch(userVolumePda);
+    assert.strictEqual(userVolume.cumulativeStake.toString(), "1010000000");
+  });
+
+  it("charges the full fee when no volume account is passed", async () => {
+    await placeBet(10_000_000, false);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.strictEqual(
+      market.feesCollected.toString(),
+      (20_000_000 + 180_000 + 200_000).toString()
+    );
+  });
+
+  describe("once the market is voided", () => {
+    async function claimRefund(withVolume: boolean) {
+      await program.methods
+        .claimRefundFromMarket()
+        .accountsPartial({
+          market: marketPda,
+          user: context.user1.publicKey,
+          userTokenAccount: context.user1TokenAccount,
+          userVolume: withVolume ? userVolumePda : null,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.user1])
+        .rpc();
+    }
+
+    before(async () => {
+      const authorityTokenAccount = getAssociatedTokenAddressSync(
+        context.mint,
+        context.authority.publicKey
+      );
+      try {
+        await createAssociatedTokenAccount(
+          context.provider.connection,
+          context.authority,
+          context.mint,
+          context.authority.publicKey
+        );
+      } catch (error) {
+        // Account might already exist, that's okay
+      }
+
+      await program.methods
+        .cancelMarket()
+        .accounts({
+          market: marketPda,
+          authority: context.authority.publicKey,
+          authorityTokenAccount,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([context.authority])
+        .rpc();
+    });
+
+    it("fails to refund without the volume account the bets counted towards", async () => {
+      try {
+        await claimRefund(false);
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        assert.ok(error.message.includes("MissingUserVolume"));
+      }
+    });
+
+    it("takes the refunded stakes back out of the user's volume", async () => {
+      await claimRefund(true);
+
+      const userVolume = await program.account.userVolume.fetch(userVolumePda);
+      assert.strictEqual(userVolume.cumulativeStake.toString(), "0");
+
+      const [positionPda] = derivePositionPda(
+        program.programId,
+        marketPda,
+        context.user1.publicKey
+      );
+      const position = await program.account.position.fetch(positionPda);
+      assert.strictEqual(position.volume.toString(), "0");
+    });
+  });
+});
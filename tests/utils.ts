@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PredictionMarket } from "../target/types/prediction_market";
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { Connection, Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import {
   createMint,
   createAccount,
@@ -78,6 +78,27 @@ export async function setupTestContext(): Promise<TestContext> {
     user2.publicKey
   );
 
+  // Every bet needs the protocol fee split, which other test files may
+  // already have set up
+  try {
+    await program.methods
+      .initializeProtocolConfig(
+        authority.publicKey,
+        PROTOCOL_SHARE_BPS,
+        REFERRER_SHARE_BPS,
+      )
+      .accountsPartial({
+        admin: authority.publicKey,
+        program: program.programId,
+        programData: deriveProgramDataPda(program.programId)[0],
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([authority])
+      .rpc();
+  } catch (error) {
+    // Config might already exist, that's okay
+  }
+
   // Mint tokens to users
   await mintTo(
     provider.connection,
@@ -118,6 +139,69 @@ export function deriveMarketPda(
   );
 }
 
+// Markets that are not tied to a football fixture are scoped to their creator
+export function deriveCreatorMarketPda(
+  programId: PublicKey,
+  authority: PublicKey,
+  marketKey: string
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("market"), authority.toBuffer(), Buffer.from(marketKey, "utf8")],
+    programId
+  );
+}
+
+// Football markets other than 1X2 append a tag + the line (i32 LE, hundredths
+// of a goal) to the seeds so they can share the fixture's game key
+function deriveFootballLineMarketPda(
+  programId: PublicKey,
+  gameKey: string,
+  tag: string,
+  line: number
+): [PublicKey, number] {
+  const lineBytes = Buffer.alloc(4);
+  lineBytes.writeInt32LE(line);
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("market"),
+      Buffer.from(gameKey, "utf8"),
+      Buffer.concat([Buffer.from(tag), lineBytes]),
+    ],
+    programId
+  );
+}
+
+export function deriveTotalGoalsMarketPda(
+  programId: PublicKey,
+  gameKey: string,
+  line: number
+): [PublicKey, number] {
+  return deriveFootballLineMarketPda(programId, gameKey, "ou", line);
+}
+
+export function deriveAsianHandicapMarketPda(
+  programId: PublicKey,
+  gameKey: string,
+  line: number
+): [PublicKey, number] {
+  return deriveFootballLineMarketPda(programId, gameKey, "ah", line);
+}
+
+export function deriveCorrectScoreMarketPda(
+  programId: PublicKey,
+  gameKey: string,
+  maxGoals: number
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("market"),
+      Buffer.from(gameKey, "utf8"),
+      Buffer.concat([Buffer.from("cs"), Buffer.from([maxGoals])]),
+    ],
+    programId
+  );
+}
+
 export function deriveVaultPda(
   programId: PublicKey,
   marketPda: PublicKey
@@ -128,6 +212,36 @@ export function deriveVaultPda(
   );
 }
 
+export function deriveOrderBookPda(
+  programId: PublicKey,
+  marketPda: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("order_book"), marketPda.toBuffer()],
+    programId
+  );
+}
+
+export function deriveProtocolConfigPda(
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_config")],
+    programId
+  );
+}
+
+export function deriveReferralPda(
+  programId: PublicKey,
+  marketPda: PublicKey,
+  referrer: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("referral"), marketPda.toBuffer(), referrer.toBuffer()],
+    programId
+  );
+}
+
 export function derivePositionPda(
   programId: PublicKey,
   marketPda: PublicKey,
@@ -139,6 +253,62 @@ export function derivePositionPda(
   );
 }
 
+export function deriveUserVolumePda(
+  programId: PublicKey,
+  user: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("user_volume"), user.toBuffer()],
+    programId
+  );
+}
+
+export function deriveParlayPoolPda(
+  programId: PublicKey,
+  authority: PublicKey,
+  mint: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("parlay_pool"), authority.toBuffer(), mint.toBuffer()],
+    programId
+  );
+}
+
+export function deriveParlayVaultPda(
+  programId: PublicKey,
+  parlayPool: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("parlay_vault"), parlayPool.toBuffer()],
+    programId
+  );
+}
+
+export function deriveParlayPda(
+  programId: PublicKey,
+  parlayPool: PublicKey,
+  user: PublicKey,
+  parlayId: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("parlay"),
+      parlayPool.toBuffer(),
+      user.toBuffer(),
+      parlayId.toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );
+}
+
+// Program data account the upgradeable loader keeps the upgrade authority in
+export function deriveProgramDataPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [programId.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+}
+
 export function getTimeValues(offsetHours: number = 1) {
   const now = Math.floor(Date.now() / 1000);
   return {
@@ -148,8 +318,69 @@ export function getTimeValues(offsetHours: number = 1) {
   };
 }
 
+// Market that starts in a few seconds and can be resolved right after, for
+// settings that have to be made before the start
+export function getImminentTimeValues(secondsUntilStart: number = 5) {
+  const now = Math.floor(Date.now() / 1000);
+  return {
+    startTime: new anchor.BN(now + secondsUntilStart),
+    endTime: new anchor.BN(now + secondsUntilStart + 1),
+    resolutionTime: new anchor.BN(now + secondsUntilStart + 2),
+  };
+}
+
+// Wait until the validator clock has passed `timestamp`
+export async function waitForTimestamp(connection: Connection, timestamp: anchor.BN) {
+  for (;;) {
+    const blockTime = await connection.getBlockTime(await connection.getSlot());
+    if (blockTime !== null && blockTime > timestamp.toNumber()) {
+      return;
+    }
+    await new Promise((resolve) => setTimeout(resolve, 500));
+  }
+}
+
 export function generateOracleFeedHash(): number[] {
   // Generate a random 32-byte array for oracle feed hash
   // Anchor expects number[] for [u8; 32] types
   return Array.from({ length: 32 }, () => Math.floor(Math.random() * 256));
 }
+
+// Fee every test market is created with, in basis points
+export const DEFAULT_FEE_BPS = 50;
+// Fee split the protocol config is initialized with, in basis points of the fee
+export const PROTOCOL_SHARE_BPS = 2_000;
+export const REFERRER_SHARE_BPS = 1_000;
+
+// Outcome indices of a football match result market, matching the oracle codes
+export const AWAY_OUTCOME = 0;
+export const HOME_OUTCOME = 1;
+export const DRAW_OUTCOME = 2;
+
+// Outcome indices of an Over/Under total goals market
+export const OVER_OUTCOME = 0;
+export const UNDER_OUTCOME = 1;
+
+// Outcome indices of an Asian handicap market
+export const HANDICAP_HOME_OUTCOME = 0;
+export const HANDICAP_AWAY_OUTCOME = 1;
+
+// Score feeds report home_goals * SCORE_ENCODING_BASE + away_goals
+export const SCORE_ENCODING_BASE = 1_000;
+
+// Outcome index of a score in a correct score market, off-grid scores are "any other"
+export function correctScoreOutcome(
+  maxGoals: number,
+  homeGoals: number,
+  awayGoals: number
+): number {
+  const side = maxGoals + 1;
+  if (homeGoals < side && awayGoals < side) {
+    return homeGoals * side + awayGoals;
+  }
+  return side * side;
+}
+
+// Outcome indices of a scalar market
+export const LONG_OUTCOME = 0;
+export const SHORT_OUTCOME = 1;